@@ -0,0 +1,309 @@
+#!/usr/bin/env python3
+"""
+Generate the small GeoTIFF test grids in this directory.
+
+The grids mirror the Gravsoft test grids `test.datum`, `test.geoid` and
+`test.deformation`, in the PROJ GeoTIFF grid profile, but exercising a
+variety of TIFF storage options: Tiled and stripped, chunky and separate
+planar configuration, DEFLATE and LZW compression, horizontal and floating
+point predictors, and little and big endian byte order.
+
+Only the Python standard library is needed: `python3 make_test_grids.py`
+"""
+
+import struct
+import zlib
+
+
+def lzw(data):
+    """TIFF flavoured LZW: MSB first, 'early change' code width increments"""
+    out, acc, nacc = bytearray(), 0, 0
+
+    def emit(code, width):
+        nonlocal acc, nacc
+        acc = (acc << width) | code
+        nacc += width
+        while nacc >= 8:
+            nacc -= 8
+            out.append((acc >> nacc) & 0xFF)
+
+    table = {bytes([i]): i for i in range(256)}
+    free, width = 258, 9
+    emit(256, width)
+    w = b""
+    for c in data:
+        wc = w + bytes([c])
+        if wc in table:
+            w = wc
+            continue
+        emit(table[w], width)
+        table[wc] = free
+        free += 1
+        if free > (1 << width) - 1:
+            width += 1
+        w = bytes([c])
+    if w:
+        emit(table[w], width)
+        free += 1
+        if free > (1 << width) - 1:
+            width += 1
+    emit(257, width)
+    if nacc:
+        out.append((acc << (8 - nacc)) & 0xFF)
+    return bytes(out)
+
+
+def pack_row(row, spp, predictor, endian):
+    """Pack one row of float32 samples, applying the predictor"""
+    n = len(row)
+    if predictor == 3:
+        raw = b"".join(struct.pack(">f", v) for v in row)
+        planes = bytearray(4 * n)
+        for k in range(n):
+            for byte in range(4):
+                planes[byte * n + k] = raw[4 * k + byte]
+        for i in range(len(planes) - 1, spp - 1, -1):
+            planes[i] = (planes[i] - planes[i - spp]) & 0xFF
+        return bytes(planes)
+    words = [struct.unpack("<I", struct.pack("<f", v))[0] for v in row]
+    if predictor == 2:
+        for i in range(n - 1, spp - 1, -1):
+            words[i] = (words[i] - words[i - spp]) & 0xFFFFFFFF
+    return b"".join(struct.pack(endian + "I", w) for w in words)
+
+
+def chunks(bands, rows, cols, g):
+    """Split the bands into compressed strips or tiles"""
+    spp = len(bands)
+    planes = [bands] if g["planar"] == 1 else [[b] for b in bands]
+    if "tile" in g:
+        cw, ch = g["tile"]
+    else:
+        cw, ch = cols, g["rows_per_strip"]
+    result = []
+    for plane in planes:
+        for r0 in range(0, rows, ch):
+            for c0 in range(0, cols, cw):
+                data = b""
+                nrows = ch if "tile" in g else min(ch, rows - r0)
+                for r in range(r0, r0 + nrows):
+                    row = []
+                    for c in range(c0, c0 + cw):
+                        for b in plane:
+                            inside = r < rows and c < cols
+                            row.append(b[r][c] if inside else 0.0)
+                    data += pack_row(row, len(plane), g["predictor"], g["endian"])
+                if g["compression"] == 8:
+                    data = zlib.compress(data)
+                elif g["compression"] == 5:
+                    data = lzw(data)
+                result.append(data)
+    return result
+
+
+def metadata(g):
+    items = "".join(
+        f'<Item name="{n}"{"" if s is None else f" sample={chr(34)}{s}{chr(34)}"}'
+        f'{"" if r is None else f" role={chr(34)}{r}{chr(34)}"}>{v}</Item>'
+        for (n, s, r, v) in g["metadata"]
+    )
+    return f"<GDALMetadata>{items}</GDALMetadata>"
+
+
+def write_tiff(path, grids, endian):
+    out = bytearray(b"II*\0" if endian == "<" else b"MM\0*")
+    out += struct.pack(endian + "I", 0)
+    next_pointer = 4
+    for g in grids:
+        g["endian"] = endian
+        bands, rows, cols = g["bands"], len(g["bands"][0]), len(g["bands"][0][0])
+        data = chunks(bands, rows, cols, g)
+        offsets = []
+        for d in data:
+            offsets.append(len(out))
+            out += d
+            if len(out) % 2:
+                out += b"\0"
+
+        # (tag, type, values)
+        entries = [
+            (256, 4, [cols]),
+            (257, 4, [rows]),
+            (258, 3, [32] * len(bands)),
+            (259, 3, [g["compression"]]),
+            (262, 3, [1]),
+            (277, 3, [len(bands)]),
+            (284, 3, [g["planar"]]),
+            (317, 3, [g["predictor"]]),
+            (339, 3, [3] * len(bands)),
+            (33550, 12, g["scale"]),
+            (33922, 12, g["tiepoint"]),
+            (34735, 3, [1, 1, 0, 3, 1024, 0, 1, 2, 1025, 0, 1, g["raster_type"], 2054, 0, 1, 9102]),
+            (42112, 2, metadata(g)),
+        ]
+        if "tile" in g:
+            entries += [
+                (322, 3, [g["tile"][0]]),
+                (323, 3, [g["tile"][1]]),
+                (324, 4, offsets),
+                (325, 4, [len(d) for d in data]),
+            ]
+        else:
+            entries += [
+                (273, 4, offsets),
+                (278, 3, [g["rows_per_strip"]]),
+                (279, 4, [len(d) for d in data]),
+            ]
+        entries.sort()
+
+        # Out-of-line tag values
+        packed = []
+        for tag, typ, values in entries:
+            if typ == 2:
+                blob = values.encode("ascii") + b"\0"
+            else:
+                fmt = {3: "H", 4: "I", 12: "d"}[typ]
+                blob = b"".join(struct.pack(endian + fmt, v) for v in values)
+            count = len(blob) if typ == 2 else len(values)
+            if len(blob) > 4:
+                offset = len(out)
+                out += blob
+                if len(out) % 2:
+                    out += b"\0"
+                packed.append((tag, typ, count, struct.pack(endian + "I", offset)))
+            else:
+                packed.append((tag, typ, count, blob.ljust(4, b"\0")))
+
+        ifd = len(out)
+        out[next_pointer : next_pointer + 4] = struct.pack(endian + "I", ifd)
+        out += struct.pack(endian + "H", len(packed))
+        for tag, typ, count, value in packed:
+            out += struct.pack(endian + "HHI", tag, typ, count) + value
+        next_pointer = len(out)
+        out += struct.pack(endian + "I", 0)
+
+    with open(path, "wb") as f:
+        f.write(out)
+
+
+# The Gravsoft test grids: 54-58N, 8-16E, 1 degree spacing
+lats = [58, 57, 56, 55, 54]
+lons = [8, 9, 10, 11, 12, 13, 14, 15, 16]
+lat_band = [[float(lat) for lon in lons] for lat in lats]
+lon_band = [[float(lon) for lon in lons] for lat in lats]
+geoid_band = [[lat + lon / 100 for lon in lons] for lat in lats]
+zero_band = [[0.0 for lon in lons] for lat in lats]
+
+POINT, AREA = 2, 1
+
+horizontal_offset = [
+    ("TYPE", None, None, "HORIZONTAL_OFFSET"),
+    ("DESCRIPTION", 0, "description", "latitude_offset"),
+    ("UNITTYPE", 0, "unittype", "arc-second"),
+    ("DESCRIPTION", 1, "description", "longitude_offset"),
+    ("UNITTYPE", 1, "unittype", "arc-second"),
+]
+
+# test_datum.tif: Tiled, separate planes, DEFLATE, floating point predictor
+write_tiff(
+    "test_datum.tif",
+    [
+        {
+            "bands": [lat_band, lon_band],
+            "planar": 2,
+            "tile": (16, 16),
+            "compression": 8,
+            "predictor": 3,
+            "scale": [1.0, 1.0, 0.0],
+            "tiepoint": [0.0, 0.0, 0.0, 8.0, 58.0, 0.0],
+            "raster_type": POINT,
+            "metadata": horizontal_offset,
+        }
+    ],
+    "<",
+)
+
+# test_geoid.tif: Big endian, stripped, LZW, no predictor, pixel-is-area
+write_tiff(
+    "test_geoid.tif",
+    [
+        {
+            "bands": [geoid_band],
+            "planar": 1,
+            "rows_per_strip": 2,
+            "compression": 5,
+            "predictor": 1,
+            "scale": [1.0, 1.0, 0.0],
+            "tiepoint": [0.0, 0.0, 0.0, 7.5, 58.5, 0.0],
+            "raster_type": AREA,
+            "metadata": [
+                ("TYPE", None, None, "VERTICAL_OFFSET_GEOGRAPHIC_TO_VERTICAL"),
+                ("DESCRIPTION", 0, "description", "geoid_undulation"),
+                ("UNITTYPE", 0, "unittype", "metre"),
+            ],
+        }
+    ],
+    ">",
+)
+
+# test_velocity.tif: Stripped, chunky planes, DEFLATE, horizontal predictor
+write_tiff(
+    "test_velocity.tif",
+    [
+        {
+            "bands": [lon_band, lat_band, zero_band],
+            "planar": 1,
+            "rows_per_strip": 3,
+            "compression": 8,
+            "predictor": 2,
+            "scale": [1.0, 1.0, 0.0],
+            "tiepoint": [0.0, 0.0, 0.0, 8.0, 58.0, 0.0],
+            "raster_type": POINT,
+            "metadata": [
+                ("TYPE", None, None, "VELOCITY"),
+                ("DESCRIPTION", 0, "description", "east_velocity"),
+                ("UNITTYPE", 0, "unittype", "millimetres per year"),
+                ("DESCRIPTION", 1, "description", "north_velocity"),
+                ("UNITTYPE", 1, "unittype", "millimetres per year"),
+                ("DESCRIPTION", 2, "description", "up_velocity"),
+                ("UNITTYPE", 2, "unittype", "millimetres per year"),
+            ],
+        }
+    ],
+    "<",
+)
+
+# test_subgrid.tif: A parent grid with a constant 1" shift, and a denser
+# child grid covering 55-56N, 12-14E, with a constant 2" shift.
+# The longitude offsets are positive west, as in NTv2
+ones = [[1.0 for lon in lons] for lat in lats]
+twos = [[2.0 for c in range(5)] for r in range(3)]
+positive_west = horizontal_offset + [("positive_value", 1, None, "west")]
+write_tiff(
+    "test_subgrid.tif",
+    [
+        {
+            "bands": [ones, ones],
+            "planar": 2,
+            "rows_per_strip": 5,
+            "compression": 1,
+            "predictor": 1,
+            "scale": [1.0, 1.0, 0.0],
+            "tiepoint": [0.0, 0.0, 0.0, 8.0, 58.0, 0.0],
+            "raster_type": POINT,
+            "metadata": positive_west,
+        },
+        {
+            "bands": [twos, twos],
+            "planar": 2,
+            "tile": (16, 16),
+            "compression": 8,
+            "predictor": 3,
+            "scale": [0.5, 0.5, 0.0],
+            "tiepoint": [0.0, 0.0, 0.0, 12.0, 56.0, 0.0],
+            "raster_type": POINT,
+            "metadata": positive_west,
+        },
+    ],
+    "<",
+)
@@ -116,6 +116,11 @@ pub enum Bibliography {
     /// [DOI](https://doi.org/10.1080/00396265.2016.1191748).
     Ruf16,
 
+    /// John P. Snyder, 1987: *Map Projections - A Working Manual*.
+    /// U.S. Geological Survey Professional Paper 1395, 383 pp.
+    /// [DOI](https://doi.org/10.3133/pp1395)
+    Sny87,
+
     /// T. Vincenty (1975) *Direct and Inverse Solutions of Geodesics on the Ellipsoid
     /// with application of nested equations*.
     /// Survey Review, 23(176): 88-93.
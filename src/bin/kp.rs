@@ -1,4 +1,5 @@
 use clap::Parser;
+use geodesy::authoring::GridInfo;
 use geodesy::prelude::*;
 use log::{info, trace}; // debug, error, warn: not used
 use std::fs::File;
@@ -15,7 +16,8 @@ use std::time;
 #[command(name = "kp")]
 #[command(author, version, about = "KP: The Rust Geodesy 'Coordinate Processing' program", long_about = None)]
 struct Cli {
-    /// The operation to carry out e.g. 'kp "utm zone=32"'
+    /// The operation to carry out e.g. 'kp "utm zone=32"', or 'grid-info'
+    /// to describe the grids given as ARGS
     operation: String,
 
     /// Inverse operation
@@ -53,11 +55,16 @@ struct Cli {
     #[clap(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
 
+    /// Additional directory to search for resources and grids. May be repeated,
+    /// with the first given taking precedence
+    #[clap(long = "data-dir", value_name = "DIR")]
+    data_dir: Vec<PathBuf>,
+
     /// Output file, stdout if not present
     #[clap(short, long)]
     _output: Option<PathBuf>,
 
-    /// The files to operate on
+    /// The files to operate on (for 'grid-info': the grid names or files)
     args: Vec<String>,
 }
 
@@ -77,6 +84,22 @@ fn main() -> Result<(), anyhow::Error> {
         eprintln!("options: {options:#?}");
     }
 
+    // Describe grids, rather than transforming coordinates
+    if options.operation == "grid-info" {
+        let ctx = context(&options);
+        for name in &options.args {
+            let grid = ctx.get_grid(name).map_err(|e| match e {
+                Error::NotFound(..) => anyhow::anyhow!(
+                    "Grid '{name}' not found: Neither in the data directories, nor as a file"
+                ),
+                e => e.into(),
+            })?;
+            println!("{name}: {} band(s)", grid.bands());
+            print_grid_info(&grid.subgrids(), 1);
+        }
+        return Ok(());
+    }
+
     // A dash, '-', given as file name indicates stdin
     if options.args.is_empty() {
         options.args.push("-".to_string());
@@ -84,7 +107,7 @@ fn main() -> Result<(), anyhow::Error> {
 
     // Create context and operator
     let start = time::Instant::now();
-    let mut ctx = Plain::new();
+    let mut ctx = context(&options);
     let duration = start.elapsed();
     trace!("Created context in: {duration:?}");
     let op = ctx.op(&options.operation)?;
@@ -171,6 +194,34 @@ fn main() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+// The context, with the search paths given by the --data-dir options added
+fn context(options: &Cli) -> Plain {
+    let mut ctx = Plain::new();
+    for dir in options.data_dir.iter().rev() {
+        ctx.add_path(dir.clone());
+    }
+    ctx
+}
+
+// Print the subgrid tree, in degrees and seconds of arc, indented by level
+fn print_grid_info(subgrids: &[GridInfo], level: usize) {
+    let indent = "  ".repeat(level);
+    for grid in subgrids {
+        let [n, s, w, e] = grid.extent.map(f64::to_degrees);
+        let [dlat, dlon] = grid.resolution.map(|r| r.to_degrees() * 3600.);
+        let name = if grid.name.is_empty() {
+            "grid"
+        } else {
+            &grid.name
+        };
+        println!(
+            "{indent}{name}: {} rows x {} cols x {} bands, N: {n:.6} S: {s:.6} W: {w:.6} E: {e:.6}, dlat: {dlat:.3}\" dlon: {dlon:.3}\"",
+            grid.rows, grid.cols, grid.bands
+        );
+        print_grid_info(&grid.children, level + 1);
+    }
+}
+
 // Transformation - this is the actual geodetic content
 fn transform(
     options: &Cli,
@@ -6,6 +6,12 @@ pub mod minimal;
 #[cfg(feature = "with_plain")]
 pub mod plain;
 
+#[cfg(feature = "with_plain")]
+pub mod shared;
+
+#[cfg(feature = "proj_db")]
+pub mod proj_db;
+
 // ----- T H E   C O N T E X T   T R A I T ---------------------------------------------
 
 /// Modes of communication between the *Rust Geodesy* internals and the external
@@ -20,6 +26,22 @@ pub trait Context {
     /// Instantiate the operation given by `definition`
     fn op(&mut self, definition: &str) -> Result<OpHandle, Error>;
 
+    /// Instantiate the operation transforming coordinates from the CRS `source`
+    /// to the CRS `target`, both given as register items, e.g. `EPSG:4326`.
+    ///
+    /// The default implementation composes the operation from two halves,
+    /// each given by a resource lookup: The resource `register:item` (with
+    /// the register name in lower case, e.g. `epsg:25832`) defines the
+    /// operation from geographic coordinates on WGS 84 (in radians, i.e. the
+    /// internal representation) to coordinates in the CRS. The inverse of the
+    /// source CRS resource, followed by the target CRS resource, then forms
+    /// the pipeline from `source` to `target`.
+    fn op_from_crs(&mut self, source: &str, target: &str) -> Result<OpHandle, Error> {
+        let source = crs_resource(self, source)?;
+        let target = crs_resource(self, target)?;
+        self.op(&format!("{source} inv | {target}"))
+    }
+
     /// Apply operation `op` to `operands`
     fn apply(
         &self,
@@ -54,6 +76,17 @@ pub trait Context {
     fn get_grid(&self, name: &str) -> Result<Arc<dyn Grid>, Error>;
 }
 
+// The name of the resource defining the CRS `id`, as used by the default
+// implementation of `Context::op_from_crs()`
+fn crs_resource<C: Context + ?Sized>(ctx: &C, id: &str) -> Result<String, Error> {
+    let Crs::RegisterItem(register, item) = id.parse()? else {
+        return Err(Error::BadParam("crs".to_string(), id.to_string()));
+    };
+    let name = format!("{}:{item}", register.to_lowercase());
+    ctx.get_resource(&name)?;
+    Ok(name)
+}
+
 /// Help context providers provide canonically named, built in coordinate adaptors
 #[rustfmt::skip]
 pub const BUILTIN_ADAPTORS: [(&str, &str); 8] = [
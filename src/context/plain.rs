@@ -1,9 +1,11 @@
 #[cfg(feature = "with_plain")]
 use crate::authoring::*;
+use crate::grid::file::FileGrid;
+use crate::grid::geotiff::GeoTiffGrid;
 use crate::grid::ntv2::Ntv2Grid;
 use std::{
     path::PathBuf,
-    sync::{Arc, Mutex, OnceLock},
+    sync::{Arc, Mutex, OnceLock, Weak},
 };
 
 // ----- T H E   P L A I N   C O N T E X T ---------------------------------------------
@@ -18,6 +20,41 @@ pub struct Plain {
     resources: BTreeMap<String, String>,
     operators: BTreeMap<OpHandle, Op>,
     paths: Vec<std::path::PathBuf>,
+    grid_size_threshold: u64,
+}
+
+/// The environment variable holding additional search paths for resources,
+/// grids and other data files, in the platform's `PATH` format. Analogous
+/// to PROJ's `PROJ_DATA`
+pub const DATA_PATH_VARIABLE: &str = "GEODESY_DATA";
+
+/// Grid files larger than this (in bytes) are read on demand, rather than
+/// into memory, if the file format supports it. See [`Plain::set_grid_size_threshold`]
+pub const DEFAULT_GRID_SIZE_THRESHOLD: u64 = 128 * 1024 * 1024;
+
+/// The default upper limit for the total size (in bytes) of the grids held
+/// in memory by the grid cache shared by all instantiations of `Plain`.
+/// See [`Plain::set_grid_cache_size`]
+pub const DEFAULT_GRID_CACHE_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Usage statistics for the grid cache shared by all instantiations of `Plain`.
+/// See [`Plain::grid_cache_stats`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GridCacheStats {
+    /// Number of grids in the cache
+    pub grids: usize,
+    /// Total (estimated) size of the grids in memory, in bytes. Besides the
+    /// grids in the cache, this includes evicted grids still in use by some
+    /// operator instantiations
+    pub bytes: u64,
+    /// The upper limit for the size of the grids in the cache
+    pub capacity: u64,
+    /// Number of grid requests served from the cache
+    pub hits: u64,
+    /// Number of grid requests needing the grid to be read
+    pub misses: u64,
+    /// Number of grids evicted to keep the cache within its capacity
+    pub evictions: u64,
 }
 
 // Helper for Plain: Provide grid access for all `Op`s
@@ -27,50 +64,279 @@ pub struct Plain {
 static GRIDS: OnceLock<Mutex<GridCollection>> = OnceLock::new();
 
 fn init_grids() -> Mutex<GridCollection> {
-    Mutex::new(GridCollection(BTreeMap::<String, Arc<dyn Grid>>::new()))
+    Mutex::new(GridCollection::new(DEFAULT_GRID_CACHE_SIZE))
+}
+
+// The cache key of a grid: The file it is read from, and whether it is read
+// on demand, rather than into memory
+type GridKey = (PathBuf, bool);
+
+// A cached grid, its size in bytes, and the time of its latest use
+struct CachedGrid {
+    grid: Arc<dyn Grid>,
+    size: u64,
+    used: u64,
+}
+
+// An evicted grid, which may still be in use by some operators, and its size
+struct EvictedGrid {
+    grid: Weak<dyn Grid>,
+    size: u64,
+}
+
+// A least recently used cache of grids, keyed by file and mode, and bounded
+// by the total size of the grids. Evicting a grid only drops the cache's
+// reference, so the memory is not released before any operators using it are
+// dropped. Until then, the grid is tracked in `evicted`, so requests for it
+// are served from there, rather than by reading a second copy
+struct GridCollection {
+    grids: BTreeMap<GridKey, CachedGrid>,
+    evicted: BTreeMap<GridKey, EvictedGrid>,
+    stats: GridCacheStats,
+    // Logical clock for ordering the grid uses
+    clock: u64,
 }
 
-struct GridCollection(BTreeMap<String, Arc<dyn Grid>>);
 impl GridCollection {
-    fn get_grid(&mut self, name: &str, paths: &[PathBuf]) -> Result<Arc<dyn Grid>, Error> {
+    fn new(capacity: u64) -> Self {
+        let stats = GridCacheStats {
+            capacity,
+            ..Default::default()
+        };
+        GridCollection {
+            grids: BTreeMap::new(),
+            evicted: BTreeMap::new(),
+            stats,
+            clock: 0,
+        }
+    }
+
+    // The grid given by `key`, if it is in the cache, or evicted, but still in use
+    fn get_grid(&mut self, key: &GridKey) -> Option<Arc<dyn Grid>> {
+        self.clock += 1;
+
         // If the grid is already there, just return a reference clone
-        if let Some(grid) = self.0.get(name) {
-            return Ok(grid.clone());
+        if let Some(cached) = self.grids.get_mut(key) {
+            cached.used = self.clock;
+            self.stats.hits += 1;
+            return Some(cached.grid.clone());
         }
 
-        // Otherwise, we must look for it in the data path
-        let n = PathBuf::from(name);
-        let ext = n
-            .extension()
-            .unwrap_or_default()
-            .to_str()
-            .unwrap_or_default();
+        // If the grid was evicted, but is still in use, bring it back
+        if let Some(evicted) = self.evicted.remove(key) {
+            if let Some(grid) = evicted.grid.upgrade() {
+                self.stats.hits += 1;
+                return Some(self.insert(key, grid, evicted.size));
+            }
+        }
+        self.stats.misses += 1;
+        None
+    }
 
-        for path in paths {
-            let mut path = path.clone();
-            path.push(ext);
-            path.push(name);
-            let Ok(grid) = std::fs::read(path) else {
-                continue;
+    // Insert the grid given by `key`, unless another thread got there first,
+    // and return the grid now in the cache
+    fn insert(&mut self, key: &GridKey, grid: Arc<dyn Grid>, size: u64) -> Arc<dyn Grid> {
+        if let Some(cached) = self.grids.get_mut(key) {
+            cached.used = self.clock;
+            return cached.grid.clone();
+        }
+        let used = self.clock;
+        self.evicted.remove(key);
+        self.grids.insert(
+            key.clone(),
+            CachedGrid {
+                grid: grid.clone(),
+                size,
+                used,
+            },
+        );
+        self.shrink(Some(key));
+        self.update_stats();
+        grid
+    }
+
+    // Total size of the grids in the cache
+    fn cached_bytes(&self) -> u64 {
+        self.grids.values().map(|cached| cached.size).sum()
+    }
+
+    // Forget the evicted grids no longer in use, and update the statistics
+    fn update_stats(&mut self) {
+        self.evicted
+            .retain(|_, evicted| evicted.grid.strong_count() > 0);
+        let evicted: u64 = self.evicted.values().map(|evicted| evicted.size).sum();
+        self.stats.grids = self.grids.len();
+        self.stats.bytes = self.cached_bytes() + evicted;
+    }
+
+    fn stats(&mut self) -> GridCacheStats {
+        self.update_stats();
+        self.stats
+    }
+
+    // Evict the least recently used grids, until the total size is within
+    // the capacity. The grid given by `keep` (if any) is never evicted
+    fn shrink(&mut self, keep: Option<&GridKey>) {
+        while self.cached_bytes() > self.stats.capacity {
+            let victim = self
+                .grids
+                .iter()
+                .filter(|(key, _)| Some(*key) != keep)
+                .min_by_key(|(_, cached)| cached.used)
+                .map(|(key, _)| key.clone());
+            let Some(victim) = victim else {
+                break;
             };
+            self.retire(&victim);
+            self.stats.evictions += 1;
+        }
+    }
 
-            if ext == "gsb" {
-                self.0
-                    .insert(name.to_string(), Arc::new(Ntv2Grid::new(&grid)?));
-            } else {
-                self.0
-                    .insert(name.to_string(), Arc::new(BaseGrid::gravsoft(&grid)?));
-            }
-            if let Some(grid) = self.0.get(name) {
-                return Ok(grid.clone());
-            }
+    // Move the grid given by `key` from the cache to the evicted grids
+    fn retire(&mut self, key: &GridKey) {
+        if let Some(cached) = self.grids.remove(key) {
+            let grid = Arc::downgrade(&cached.grid);
+            let size = cached.size;
+            self.evicted.insert(key.clone(), EvictedGrid { grid, size });
+        }
+    }
+
+    // Evict all grids read from files named `name`, or, if `name` has more
+    // than one component, from files with paths ending in `name`
+    fn evict(&mut self, name: &str) -> bool {
+        let keys: Vec<GridKey> = self
+            .grids
+            .keys()
+            .filter(|(path, _)| path.ends_with(name))
+            .cloned()
+            .collect();
+        for key in &keys {
+            self.retire(key);
         }
-        Err(Error::NotFound(name.to_string(), ": Grid".to_string()))
+        self.update_stats();
+        !keys.is_empty()
+    }
+
+    fn clear(&mut self) {
+        let keys: Vec<GridKey> = self.grids.keys().cloned().collect();
+        for key in &keys {
+            self.retire(key);
+        }
+        self.update_stats();
+    }
+
+    fn set_capacity(&mut self, capacity: u64) {
+        self.stats.capacity = capacity;
+        self.shrink(None);
+        self.update_stats();
+    }
+}
+
+// Get the grid `name` through the cache `grids`. The lock on the cache is
+// not held while reading the grid, so other grid requests are not held up
+// by the reading of a large grid
+fn get_grid(
+    grids: &Mutex<GridCollection>,
+    name: &str,
+    paths: &[PathBuf],
+    threshold: u64,
+) -> Result<Arc<dyn Grid>, Error> {
+    let key = locate_grid(name, paths, threshold)?;
+    if let Some(grid) = grids.lock().unwrap().get_grid(&key) {
+        return Ok(grid);
+    }
+    let grid = read_grid(name, &key)?;
+    let size = if key.1 {
+        0
+    } else {
+        grid_size(&grid.subgrids())
+    };
+    Ok(grids.lock().unwrap().insert(&key, grid, size))
+}
+
+// The file holding the grid `name`: The first of the `paths` holding it, or,
+// if none of them do, the file `name` itself. Also determines whether the
+// grid is to be read on demand, rather than into memory
+fn locate_grid(name: &str, paths: &[PathBuf], threshold: u64) -> Result<GridKey, Error> {
+    let n = PathBuf::from(name);
+    let ext = grid_extension(name);
+
+    let candidates = paths.iter().map(|path| path.join(ext).join(name));
+    let Some(path) = candidates
+        .chain(std::iter::once(n))
+        .find(|path| path.is_file())
+    else {
+        return Err(Error::NotFound(name.to_string(), ": Grid".to_string()));
+    };
+
+    // Large grids in formats supporting random access are read on demand
+    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let on_demand = size > threshold && (ext == "gtx" || ext == "byn");
+    let path = std::fs::canonicalize(&path).unwrap_or(path);
+    Ok((path, on_demand))
+}
+
+fn grid_extension(name: &str) -> &str {
+    std::path::Path::new(name)
+        .extension()
+        .unwrap_or_default()
+        .to_str()
+        .unwrap_or_default()
+}
+
+// Read the grid `name` from the file, and in the mode, given by `key`
+fn read_grid(name: &str, key: &GridKey) -> Result<Arc<dyn Grid>, Error> {
+    let (path, on_demand) = key;
+    let ext = grid_extension(name);
+    if *on_demand {
+        return Ok(if ext == "gtx" {
+            Arc::new(FileGrid::gtx(path)?)
+        } else {
+            Arc::new(FileGrid::byn(path)?)
+        });
     }
+
+    let grid = std::fs::read(path)?;
+    Ok(match ext {
+        "gsb" => Arc::new(Ntv2Grid::new(&grid)?),
+        "tif" | "tiff" => Arc::new(GeoTiffGrid::new(&grid)?),
+        "gtx" => Arc::new(BaseGrid::gtx(&grid)?),
+        "byn" => Arc::new(BaseGrid::byn(&grid)?),
+        "isg" => Arc::new(BaseGrid::isg(&grid)?),
+        _ => Arc::new(BaseGrid::gravsoft(&grid)?),
+    })
+}
+
+// The size of the grid values of a subgrid tree, in bytes (32 bit floats)
+fn grid_size(subgrids: &[GridInfo]) -> u64 {
+    subgrids
+        .iter()
+        .map(|g| (g.rows * g.cols * g.bands * 4) as u64 + grid_size(&g.children))
+        .sum()
 }
 
 const BAD_ID_MESSAGE: Error = Error::General("Plain: Unknown operator id");
 
+// The search paths: The `geodesy` subdirectory of the current directory, the
+// directories given by the `DATA_PATH_VARIABLE` environment variable, and
+// the `geodesy` subdirectory of the local data directory, in that order
+fn default_paths(variable: Option<std::ffi::OsString>) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    let localpath: PathBuf = [".", "geodesy"].iter().collect();
+    paths.push(localpath);
+
+    if let Some(variable) = variable {
+        paths.extend(std::env::split_paths(&variable).filter(|p| !p.as_os_str().is_empty()));
+    }
+
+    if let Some(mut userpath) = dirs::data_local_dir() {
+        userpath.push("geodesy");
+        paths.push(userpath);
+    }
+    paths
+}
+
 impl Plain {
     /// To avoid having the heap allocated collection of grids stored in `GRIDS`
     /// growing through the roof, we may clear it occasionally.
@@ -79,8 +345,87 @@ impl Plain {
     /// instantiations.
     pub fn clear_grids() {
         if let Some(grids) = GRIDS.get() {
-            grids.lock().unwrap().0.clear();
+            grids.lock().unwrap().clear();
+        }
+    }
+
+    /// Remove the grid `name` from the grid cache, shared by all instantiations
+    /// of `Plain`. Grids are cached by the file they are read from, so this
+    /// removes the grids read from any file named `name` (or, if `name` is a
+    /// path, from files with paths ending in `name`). Returns `false` if no
+    /// such grid was in the cache. As for
+    /// [`Plain::clear_grids`], operators using the grid are not affected, and
+    /// while they live, the grid is reused, rather than reread, if requested again.
+    pub fn evict_grid(name: &str) -> bool {
+        GRIDS.get_or_init(init_grids).lock().unwrap().evict(name)
+    }
+
+    /// Set the upper limit for the total size (in bytes) of the grids held in
+    /// memory by the grid cache, shared by all instantiations of `Plain`.
+    /// Default [`DEFAULT_GRID_CACHE_SIZE`]. When exceeded, the least recently
+    /// used grids are evicted. Grids read on demand (see
+    /// [`Plain::set_grid_size_threshold`]) do not count, and a single grid
+    /// larger than the limit is retained until another grid is loaded.
+    pub fn set_grid_cache_size(bytes: u64) {
+        GRIDS
+            .get_or_init(init_grids)
+            .lock()
+            .unwrap()
+            .set_capacity(bytes);
+    }
+
+    /// Usage statistics for the grid cache, shared by all instantiations of `Plain`
+    pub fn grid_cache_stats() -> GridCacheStats {
+        GRIDS.get_or_init(init_grids).lock().unwrap().stats()
+    }
+
+    /// Grid files larger than `threshold` bytes are read on demand, rather
+    /// than into memory, when the grid format supports random access (currently
+    /// the GTX and BYN geoid model formats). Default [`DEFAULT_GRID_SIZE_THRESHOLD`].
+    /// Since grids are shared between all instantiations of `Plain`, the
+    /// threshold only affects grids not already loaded
+    pub fn set_grid_size_threshold(&mut self, threshold: u64) {
+        self.grid_size_threshold = threshold;
+    }
+
+    /// Replace the search paths for resources, grids and other data files.
+    /// Each path is expected to hold subdirectories named by content, i.e.
+    /// `resources` for resources, and by file extension for grids (e.g. `gsb`)
+    pub fn with_paths<P: Into<PathBuf>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add a search path, taking precedence over the existing ones.
+    /// Since grids are shared between all instantiations of `Plain`, by
+    /// name, already loaded grids are not affected
+    pub fn add_path(&mut self, path: impl Into<PathBuf>) {
+        self.paths.insert(0, path.into());
+    }
+
+    /// The search paths, in order of precedence
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    // The first existing file named `name`, in the subdirectory named by the
+    // file extension, of any of the search paths
+    pub(crate) fn find_file(&self, name: &str) -> Option<PathBuf> {
+        let n = PathBuf::from(name);
+        let ext = n
+            .extension()
+            .unwrap_or_default()
+            .to_str()
+            .unwrap_or_default();
+        for path in &self.paths {
+            let mut path = path.clone();
+            path.push(ext);
+            path.push(name);
+            if path.is_file() {
+                return Some(path);
+            }
         }
+        None
     }
 }
 
@@ -89,21 +434,14 @@ impl Default for Plain {
         let constructors = BTreeMap::new();
         let resources = BTreeMap::new();
         let operators = BTreeMap::new();
-        let mut paths = Vec::new();
-
-        let localpath: PathBuf = [".", "geodesy"].iter().collect();
-        paths.push(localpath);
-
-        if let Some(mut userpath) = dirs::data_local_dir() {
-            userpath.push("geodesy");
-            paths.push(userpath);
-        }
+        let paths = default_paths(std::env::var_os(DATA_PATH_VARIABLE));
 
         Plain {
             constructors,
             resources,
             operators,
             paths,
+            grid_size_threshold: DEFAULT_GRID_SIZE_THRESHOLD,
         }
     }
 }
@@ -253,33 +591,26 @@ impl Context for Plain {
     }
 
     fn get_blob(&self, name: &str) -> Result<Vec<u8>, Error> {
-        let n = PathBuf::from(name);
-        let ext = n
-            .extension()
-            .unwrap_or_default()
-            .to_str()
-            .unwrap_or_default();
-        for path in &self.paths {
-            let mut path = path.clone();
-            path.push(ext);
-            path.push(name);
-            if let Ok(result) = std::fs::read(path) {
-                return Ok(result);
-            }
+        if let Some(path) = self.find_file(name) {
+            return Ok(std::fs::read(path)?);
         }
         Err(Error::NotFound(name.to_string(), ": Blob".to_string()))
     }
 
-    /// Access grid resources by identifier
+    /// Access grid resources by identifier. The grid is looked up in the
+    /// `ext` subdirectory (where `ext` is the extension of `name`) of each
+    /// of the search paths. If not found there, `name` is taken as the path
+    /// to the grid file
     fn get_grid(&self, name: &str) -> Result<Arc<dyn Grid>, Error> {
         // The GridCollection does all the hard work here, but accessing GRIDS,
         // which is a mutable static is (mis-)diagnosed as unsafe by the compiler,
         // even though the mutable static is behind a Mutex guard
-        GRIDS
-            .get_or_init(init_grids)
-            .lock()
-            .unwrap()
-            .get_grid(name, &self.paths)
+        get_grid(
+            GRIDS.get_or_init(init_grids),
+            name,
+            &self.paths,
+            self.grid_size_threshold,
+        )
     }
 }
 
@@ -382,6 +713,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn registers() -> Result<(), Error> {
+        let mut ctx = Plain::new();
+
+        // Gauss-Krüger grids from geodesy/resources/gk.md
+        let op = ctx.op("gk:dhdn zone=4")?;
+        let mut data = [Coor2D::geo(55., 12.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert_float_eq!(data[0].0, [4_500_000., 6_096_598.929_73], abs_all <= 1e-5);
+        assert!(ctx.op("gk:dhdn").is_err());
+
+        let op = ctx.op("gk:pulkovo zone=5")?;
+        let mut data = [Coor2D::geo(50., 27.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert_float_eq!(data[0][0], 5_500_000., abs <= 1e-6);
+
+        // State plane zones from geodesy/resources/spcs.md: The origin maps
+        // to the false easting and northing
+        #[rustfmt::skip]
+        let zones = [
+            ("spcs:ca_3",          36.5,              -120.5,             2_000_000.,      500_000.),
+            ("spcs:co_north",      39.333_333_333_33, -105.5,               914_401.828_9, 304_800.609_6),
+            ("spcs:fl_east",       24.333_333_333_33,  -81.,                200_000.,            0.),
+            ("spcs:il_east",       36.666_666_666_67,  -88.333_333_333_33,  300_000.,            0.),
+            ("spcs:ny_east",       38.833_333_333_33,  -74.5,               150_000.,            0.),
+            ("spcs:ny_long_island",40.166_666_666_67,  -74.,                300_000.,            0.),
+            ("spcs:pa_north",      40.166_666_666_67,  -77.75,              600_000.,            0.),
+            ("spcs:tx_central",    29.666_666_666_67, -100.333_333_333_33,  700_000.,    3_000_000.),
+            ("spcs:wa_north",      47.,               -120.833_333_333_33,  500_000.,            0.),
+        ];
+        for (name, lat, lon, x, y) in zones {
+            let op = ctx.op(name)?;
+            let mut data = [Coor2D::geo(lat, lon)];
+            ctx.apply(op, Fwd, &mut data)?;
+            assert_float_eq!(data[0].0, [x, y], abs_all <= 1e-3);
+        }
+        Ok(())
+    }
+
     #[test]
     fn grids() -> Result<(), Error> {
         let mut ctx = Plain::new();
@@ -395,4 +765,189 @@ mod tests {
         assert!(ctx.op("gridshift grids=non.existing").is_err());
         Ok(())
     }
+
+    #[test]
+    fn file_grids() -> Result<(), Error> {
+        // Use a private GridCollection, to avoid interference from grids
+        // already loaded into the shared one by other tests
+        let grids = Mutex::new(GridCollection::new(DEFAULT_GRID_CACHE_SIZE));
+        let paths = Plain::default().paths;
+
+        // Below the threshold, grids are read into memory
+        let (_, on_demand) = locate_grid("test.gtx", &paths, DEFAULT_GRID_SIZE_THRESHOLD)?;
+        assert!(!on_demand);
+
+        // Above, they are read on demand
+        let (_, on_demand) = locate_grid("test.gtx", &paths, 0)?;
+        assert!(on_demand);
+        let (_, on_demand) = locate_grid("test.byn", &paths, 0)?;
+        assert!(on_demand);
+
+        // ...but only for formats supporting random access
+        let (_, on_demand) = locate_grid("test.isg", &paths, 0)?;
+        assert!(!on_demand);
+
+        // Grids not found in the search paths are read from the path given
+        let key = locate_grid("geodesy/gsb/5458.gsb", &paths, 0)?;
+        assert_eq!(read_grid("geodesy/gsb/5458.gsb", &key)?.bands(), 2);
+        assert!(locate_grid("5458.gsb", &[], 0).is_err());
+
+        // Grids read on demand take up no room in the cache
+        get_grid(&grids, "test.gtx", &paths, 0)?;
+        assert_eq!(grids.lock().unwrap().stats().bytes, 0);
+
+        let c = Coor4D::geo(55., 12., 0., 0.);
+        let byn = get_grid(&grids, "test.byn", &paths, 0)?;
+        assert!((byn.at(&c, 0.).unwrap()[0] - 55.12).abs() < 1e-5);
+
+        // The threshold is configurable per Plain instance
+        let mut ctx = Plain::new();
+        ctx.set_grid_size_threshold(0);
+        assert_eq!(ctx.grid_size_threshold, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn grid_cache() -> Result<(), Error> {
+        let paths = Plain::default().paths;
+        let threshold = DEFAULT_GRID_SIZE_THRESHOLD;
+
+        // test.geoid and test.datum are 5 x 9 grids of 1 and 2 bands
+        let grids = Mutex::new(GridCollection::new(DEFAULT_GRID_CACHE_SIZE));
+        let stats = || grids.lock().unwrap().stats();
+        let cached = |name: &str| {
+            let key = locate_grid(name, &paths, threshold).unwrap();
+            grids.lock().unwrap().grids.contains_key(&key)
+        };
+        get_grid(&grids, "test.geoid", &paths, threshold)?;
+        get_grid(&grids, "test.datum", &paths, threshold)?;
+        get_grid(&grids, "test.geoid", &paths, threshold)?;
+        assert_eq!((stats().grids, stats().bytes), (2, 45 * 4 * 3));
+        assert_eq!((stats().hits, stats().misses, stats().evictions), (1, 2, 0));
+
+        // Shrinking the capacity evicts the least recently used grid
+        grids.lock().unwrap().set_capacity(45 * 4 * 2);
+        assert_eq!((stats().grids, stats().bytes), (1, 45 * 4));
+        assert!(cached("test.geoid"));
+
+        // ...and so does loading a grid beyond the capacity
+        get_grid(&grids, "test.datum", &paths, threshold)?;
+        assert_eq!((stats().grids, stats().bytes), (1, 45 * 4 * 2));
+        assert!(cached("test.datum"));
+        assert_eq!(stats().evictions, 2);
+
+        // Grids read on demand are not counted
+        get_grid(&grids, "test.gtx", &paths, 0)?;
+        assert_eq!((stats().grids, stats().bytes), (2, 45 * 4 * 2));
+
+        // Explicit eviction, by file name or path
+        assert!(grids.lock().unwrap().evict("test.datum"));
+        assert!(!grids.lock().unwrap().evict("test.datum"));
+        assert_eq!((stats().grids, stats().bytes), (1, 0));
+        get_grid(&grids, "test.datum", &paths, threshold)?;
+        assert!(grids.lock().unwrap().evict("datum/test.datum"));
+
+        // Evicted grids still in use are counted, and reused rather than reread
+        let datum = get_grid(&grids, "test.datum", &paths, threshold)?;
+        assert!(grids.lock().unwrap().evict("test.datum"));
+        assert_eq!((stats().grids, stats().bytes), (1, 45 * 4 * 2));
+        let misses = stats().misses;
+        let again = get_grid(&grids, "test.datum", &paths, threshold)?;
+        assert!(Arc::ptr_eq(&datum, &again));
+        assert_eq!(stats().misses, misses);
+        assert_eq!((stats().grids, stats().bytes), (2, 45 * 4 * 2));
+
+        // ...until the operators using them are gone
+        grids.lock().unwrap().clear();
+        assert_eq!((stats().grids, stats().bytes), (0, 45 * 4 * 2));
+        drop((datum, again));
+        assert_eq!((stats().grids, stats().bytes), (0, 0));
+
+        // The same name in other paths, or read in another mode, is another grid
+        let dir = std::env::temp_dir().join(format!("geodesy-grid-cache-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("gtx"))?;
+        std::fs::copy("geodesy/gtx/test.gtx", dir.join("gtx/test.gtx"))?;
+        let gtx = get_grid(&grids, "test.gtx", &paths, threshold)?;
+        let other = get_grid(&grids, "test.gtx", std::slice::from_ref(&dir), threshold)?;
+        let on_demand = get_grid(&grids, "test.gtx", &paths, 0)?;
+        assert!(!Arc::ptr_eq(&gtx, &other));
+        assert!(!Arc::ptr_eq(&gtx, &on_demand));
+        assert_eq!(stats().grids, 3);
+        assert!(Arc::ptr_eq(
+            &gtx,
+            &get_grid(&grids, "./geodesy/gtx/test.gtx", &[], threshold)?
+        ));
+        std::fs::remove_dir_all(dir)?;
+
+        // The shared cache is reachable through Plain
+        Plain::set_grid_cache_size(DEFAULT_GRID_CACHE_SIZE);
+        assert_eq!(Plain::grid_cache_stats().capacity, DEFAULT_GRID_CACHE_SIZE);
+        assert!(!Plain::evict_grid("no such grid"));
+        Ok(())
+    }
+
+    #[test]
+    fn paths() -> Result<(), Error> {
+        // Additional paths from the environment go between the local and the user paths
+        let paths = default_paths(Some(std::env::join_paths(["foo", "bar"]).unwrap()));
+        assert_eq!(paths[0], PathBuf::from("./geodesy"));
+        assert_eq!(paths[1..3], [PathBuf::from("foo"), PathBuf::from("bar")]);
+        assert_eq!(default_paths(None).len(), paths.len() - 2);
+
+        // Nothing to be found, when looking in the wrong places...
+        let mut ctx = Plain::new().with_paths(["foo", "bar"]);
+        assert_eq!(ctx.paths(), [PathBuf::from("foo"), PathBuf::from("bar")]);
+        assert!(ctx.get_resource("stupid:way").is_err());
+        assert!(ctx.get_blob("test.gtx").is_err());
+
+        // ...but added paths take precedence
+        ctx.add_path("geodesy");
+        assert_eq!(ctx.paths()[0], PathBuf::from("geodesy"));
+        assert!(ctx.get_resource("stupid:way").is_ok());
+        assert!(ctx.get_blob("test.gtx").is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn op_from_crs() -> Result<(), Error> {
+        let mut ctx = Plain::new();
+
+        // The CRS halves are looked up in geodesy/resources/epsg.md
+        let op = ctx.op_from_crs("EPSG:4326", "EPSG:25832")?;
+        let utm = ctx.op("geo:in | utm zone=32")?;
+        let mut data = [Coor4D::raw(55., 12., 0., 0.)];
+        let mut expected = data;
+        ctx.apply(op, Fwd, &mut data)?;
+        ctx.apply(utm, Fwd, &mut expected)?;
+        assert_eq!(data, expected);
+        ctx.apply(op, Inv, &mut data)?;
+        assert!(data[0].hypot2(&Coor4D::raw(55., 12., 0., 0.)) < 1e-12);
+
+        // Datum shift between projected CRSs
+        let op = ctx.op_from_crs("EPSG:23032", "EPSG:25832")?;
+        let explicit = ctx.op(
+            "inv utm zone=32 ellps=intl | cart ellps=intl | helmert x=-87 y=-98 z=-121 | inv cart ellps=WGS84 | utm zone=32",
+        )?;
+        let mut data = [Coor4D::raw(700000., 6100000., 0., 0.)];
+        let mut expected = data;
+        ctx.apply(op, Fwd, &mut data)?;
+        ctx.apply(explicit, Fwd, &mut expected)?;
+        assert!(data[0].hypot2(&expected[0]) < 1e-6);
+
+        // Northing before easting
+        let op = ctx.op_from_crs("EPSG:4258", "EPSG:3006")?;
+        let mut data = [Coor4D::raw(59., 15., 0., 0.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][1] - 500000.).abs() < 1e-6);
+
+        assert!(matches!(
+            ctx.op_from_crs("EPSG:4326", "EPSG:1"),
+            Err(Error::NotFound(_, _))
+        ));
+        assert!(matches!(
+            ctx.op_from_crs("4326", "EPSG:25832"),
+            Err(Error::BadParam(_, _))
+        ));
+        Ok(())
+    }
 }
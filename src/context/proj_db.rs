@@ -0,0 +1,798 @@
+//! A context provider resolving CRS identifiers, like `EPSG:25832`, using
+//! a local copy of the PROJ database, `proj.db`.
+//!
+//! Only a subset of the database content is supported: Geographic, geocentric
+//! and projected CRSs, with projections given by one of the conversion
+//! methods in `METHODS`, and datum shifts given as Helmert transformations.
+//! Everything else, including grid based transformations and datum ensemble
+//! accuracies, is ignored or rejected as unsupported.
+use crate::authoring::*;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use std::{path::Path, sync::Arc};
+
+// ----- T H E   P R O J _ D B   C O N T E X T -----------------------------------------
+
+/// A context provider, extending [`Plain`] with the ability to build
+/// pipelines between pairs of CRSs, from their definitions in PROJ's
+/// `proj.db` SQLite database.
+///
+/// The database is read from a local file, so no network access is needed.
+/// [`ProjDb::open`] opens a given database file, while [`Context::new`] looks
+/// for `db/proj.db` in the search paths of `Plain` (i.e. `./geodesy`, the
+/// directories given by the `GEODESY_DATA` environment variable, and the
+/// `geodesy` subdirectory of the local data directory).
+///
+/// Many pairs of datums are related by several Helmert transformations, each
+/// valid for its own area. [`ProjDb::with_area_of_interest`] selects the one
+/// for the most specific area covering the area of interest. Without an area
+/// of interest, datum shifts between such pairs are rejected as ambiguous.
+#[derive(Debug)]
+pub struct ProjDb {
+    plain: Plain,
+    db: Option<Connection>,
+    // West, south, east, north, in degrees
+    area: Option<[f64; 4]>,
+}
+
+impl ProjDb {
+    /// Open the database file at `path`, read only
+    pub fn open(path: &Path) -> Result<ProjDb, Error> {
+        let db = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        Ok(ProjDb {
+            plain: Plain::new(),
+            db: Some(db),
+            area: None,
+        })
+    }
+
+    /// Select datum shifts valid for the area bounded by the longitudes `west`
+    /// and `east`, and the latitudes `south` and `north`, all in degrees
+    pub fn with_area_of_interest(mut self, west: f64, south: f64, east: f64, north: f64) -> Self {
+        self.area = Some([west, south, east, north]);
+        self
+    }
+
+    /// The Rust Geodesy definition of the pipeline transforming coordinates
+    /// in the CRS `source` to the CRS `target`, both given as `authority:code`,
+    /// e.g. `EPSG:4326`. The pipeline handles the axis order and units of
+    /// both CRSs, so it may be instantiated and applied directly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use geodesy::prelude::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let mut ctx = ProjDb::open(std::path::Path::new("proj.db"))?;
+    /// let definition = ctx.definition("EPSG:4326", "EPSG:25832")?;
+    /// let op = ctx.op(&definition)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn definition(&self, source: &str, target: &str) -> Result<String, Error> {
+        let source = self.crs(source)?;
+        let target = self.crs(target)?;
+
+        // The PROJ steps, from source CRS coordinates to target CRS coordinates
+        let mut steps = Vec::new();
+        if let Some(projection) = &source.projection {
+            steps.push(format!("step inv {projection}"));
+        }
+        if source.datum != target.datum {
+            steps.extend(self.datum_shift(&source, &target)?);
+        }
+        if let Some(projection) = &target.projection {
+            steps.push(format!("step {projection}"));
+        }
+
+        let core = if steps.is_empty() {
+            String::new()
+        } else {
+            parse_proj(&format!("proj=pipeline {}", steps.join(" ")))?
+        };
+
+        let definition: Vec<&str> = [source.axes.0, &core, target.axes.1]
+            .into_iter()
+            .filter(|step| !step.is_empty())
+            .collect();
+        if definition.is_empty() {
+            return Ok("noop".to_string());
+        }
+        Ok(definition.join(" | "))
+    }
+
+    fn db(&self) -> Result<&Connection, Error> {
+        self.db.as_ref().ok_or(Error::NotFound(
+            "proj.db".to_string(),
+            ": Database".to_string(),
+        ))
+    }
+
+    // Collect the parts of the definition of the CRS `id` needed for building pipelines
+    fn crs(&self, id: &str) -> Result<CrsDefinition, Error> {
+        let (auth, code) = id.split_once(':').ok_or_else(|| {
+            Error::BadParam("needing authority:code format".to_string(), id.to_string())
+        })?;
+        let db = self.db()?;
+
+        // A projected CRS is a conversion of its base geodetic CRS
+        let projected: Option<(Id, Id, Id)> = db
+            .query_row(
+                "SELECT coordinate_system_auth_name, coordinate_system_code,
+                        geodetic_crs_auth_name, geodetic_crs_code,
+                        conversion_auth_name, conversion_code
+                 FROM projected_crs WHERE auth_name = ?1 AND code = ?2",
+                [auth, code],
+                |r| {
+                    let cs = (r.get(0)?, r.get(1)?);
+                    let geodetic = (r.get(2)?, r.get(3)?);
+                    let conversion = (r.get(4)?, r.get(5)?);
+                    Ok((cs, geodetic, conversion))
+                },
+            )
+            .optional()?;
+        let (geodetic, projected) = match projected {
+            Some((cs, geodetic, conversion)) => (geodetic, Some((cs, conversion))),
+            None => ((auth.to_string(), code.to_string()), None),
+        };
+
+        let (kind, cs, datum): (String, Id, Id) = db
+            .query_row(
+                "SELECT type, coordinate_system_auth_name, coordinate_system_code,
+                        datum_auth_name, datum_code
+                 FROM geodetic_crs WHERE auth_name = ?1 AND code = ?2",
+                [&geodetic.0, &geodetic.1],
+                |r| Ok((r.get(0)?, (r.get(1)?, r.get(2)?), (r.get(3)?, r.get(4)?))),
+            )
+            .optional()?
+            .ok_or_else(|| Error::NotFound(id.to_string(), ": CRS".to_string()))?;
+
+        let ellps = self.ellipsoid(&datum)?;
+
+        let (axes, projection) = match projected {
+            Some((cs, conversion)) => {
+                let projection = self.conversion(&conversion)?;
+                let axes = match self.axes(&cs)?.as_slice() {
+                    [("east", METRE), ("north", METRE), ..] => ("", ""),
+                    [("north", METRE), ("east", METRE), ..] => ("neu:in", "neu:out"),
+                    _ => return Err(Error::Unsupported(format!("{id}: Axis order or units"))),
+                };
+                (axes, Some(format!("{projection} {ellps}")))
+            }
+            None if kind == "geocentric" => (("", ""), Some(format!("proj=cart {ellps}"))),
+            None => {
+                let axes = match self.axes(&cs)?.as_slice() {
+                    [("north", DEGREE), ("east", DEGREE), ..] => ("geo:in", "geo:out"),
+                    [("east", DEGREE), ("north", DEGREE), ..] => ("gis:in", "gis:out"),
+                    _ => return Err(Error::Unsupported(format!("{id}: Axis order or units"))),
+                };
+                (axes, None)
+            }
+        };
+
+        Ok(CrsDefinition {
+            datum,
+            ellps,
+            axes,
+            projection,
+        })
+    }
+
+    // The ellipsoid of a datum, as PROJ `a` and `rf` parameters
+    fn ellipsoid(&self, datum: &Id) -> Result<String, Error> {
+        let (a, rf, b, uom, meridian): (f64, Option<f64>, Option<f64>, String, String) = self
+            .db()?
+            .query_row(
+                "SELECT e.semi_major_axis, e.inv_flattening, e.semi_minor_axis,
+                        e.uom_code, d.prime_meridian_code
+                 FROM geodetic_datum d JOIN ellipsoid e
+                 ON e.auth_name = d.ellipsoid_auth_name AND e.code = d.ellipsoid_code
+                 WHERE d.auth_name = ?1 AND d.code = ?2",
+                [&datum.0, &datum.1],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)),
+            )
+            .optional()?
+            .ok_or_else(|| {
+                Error::NotFound(format!("{}:{}", datum.0, datum.1), ": Datum".to_string())
+            })?;
+
+        if uom != METRE || meridian != GREENWICH {
+            return Err(Error::Unsupported(format!(
+                "{}:{}: Ellipsoid units or prime meridian",
+                datum.0, datum.1
+            )));
+        }
+
+        // Spheres and some ellipsoids are given by their semi-minor axis
+        let rf = match (rf, b) {
+            (Some(rf), _) => rf,
+            (None, Some(b)) if b == a => 0.,
+            (None, Some(b)) => a / (a - b),
+            _ => {
+                return Err(Error::Invalid(format!(
+                    "{}:{}: Ellipsoid",
+                    datum.0, datum.1
+                )))
+            }
+        };
+        Ok(format!("a={a} rf={rf}"))
+    }
+
+    // Orientation and unit of each axis of a coordinate system
+    fn axes(&self, cs: &Id) -> Result<Vec<(&'static str, &'static str)>, Error> {
+        let mut statement = self.db()?.prepare(
+            "SELECT orientation, uom_code FROM axis
+             WHERE coordinate_system_auth_name = ?1 AND coordinate_system_code = ?2
+             ORDER BY coordinate_system_order",
+        )?;
+        let rows = statement.query_map([&cs.0, &cs.1], |r| {
+            Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?))
+        })?;
+
+        // We only need to distinguish the few cases we support
+        let mut axes = Vec::new();
+        for row in rows {
+            let (orientation, uom) = row?;
+            let orientation = match orientation.as_str() {
+                "north" => "north",
+                "east" => "east",
+                _ => "other",
+            };
+            let uom = match uom.as_str() {
+                METRE => METRE,
+                "9102" | "9122" => DEGREE,
+                _ => "other",
+            };
+            axes.push((orientation, uom));
+        }
+        Ok(axes)
+    }
+
+    // The PROJ definition of a conversion (i.e. a projection), sans ellipsoid
+    fn conversion(&self, conversion: &Id) -> Result<String, Error> {
+        let id = format!("{}:{}", conversion.0, conversion.1);
+        let columns: Vec<String> = (1..=7)
+            .map(|i| format!("param{i}_code, param{i}_value, param{i}_uom_code"))
+            .collect();
+        let query = format!(
+            "SELECT method_code, {} FROM conversion WHERE auth_name = ?1 AND code = ?2",
+            columns.join(", ")
+        );
+
+        let (method, parameters) = self
+            .db()?
+            .query_row(&query, [&conversion.0, &conversion.1], |r| {
+                let mut parameters = Vec::new();
+                for i in 0..7 {
+                    let code: Option<String> = r.get(1 + 3 * i)?;
+                    let value: Option<f64> = r.get(2 + 3 * i)?;
+                    let uom: Option<String> = r.get(3 + 3 * i)?;
+                    if let (Some(code), Some(value)) = (code, value) {
+                        parameters.push((code, value, uom.unwrap_or_default()));
+                    }
+                }
+                Ok((r.get::<_, String>(0)?, parameters))
+            })
+            .optional()?
+            .ok_or_else(|| Error::NotFound(id.clone(), ": Conversion".to_string()))?;
+
+        let Some((_, name, names)) = METHODS.iter().find(|m| m.0 == method) else {
+            return Err(Error::Unsupported(format!(
+                "{id}: Conversion method {method}"
+            )));
+        };
+
+        let mut definition = format!("proj={name}");
+        for (code, key) in names.iter() {
+            let Some((_, value, uom)) = parameters.iter().find(|p| p.0 == *code) else {
+                return Err(Error::MissingParam(format!("{id}: {code}")));
+            };
+            definition += &format!(" {key}={}", to_proj_units(*value, uom)?);
+        }
+        Ok(definition)
+    }
+
+    // The PROJ steps for the datum shift between the datums of `source` and
+    // `target`: Either a direct Helmert transformation, or a Helmert
+    // transformation to WGS 84, followed by one from WGS 84
+    fn datum_shift(
+        &self,
+        source: &CrsDefinition,
+        target: &CrsDefinition,
+    ) -> Result<Vec<String>, Error> {
+        let wgs84 = ("EPSG".to_string(), "6326".to_string());
+        let helmert = if let Some(direct) = self.helmert(&source.datum, &target.datum)? {
+            vec![direct]
+        } else {
+            let first = self.helmert(&source.datum, &wgs84)?;
+            let second = self.helmert(&wgs84, &target.datum)?;
+            let (Some(first), Some(second)) = (first, second) else {
+                return Err(Error::NotFound(
+                    format!(
+                        "{}:{} to {}:{}",
+                        source.datum.0, source.datum.1, target.datum.0, target.datum.1
+                    ),
+                    ": Datum shift".to_string(),
+                ));
+            };
+            vec![first, second]
+        };
+
+        let mut steps = vec![format!("step proj=cart {}", source.ellps)];
+        steps.extend(helmert.iter().map(|h| format!("step {h}")));
+        steps.push(format!("step inv proj=cart {}", target.ellps));
+        Ok(steps)
+    }
+
+    // The Helmert transformation between two datums, if any, applied in the
+    // reverse direction if only that is registered. With an area of interest,
+    // we select the most accurate of those for the smallest area covering it.
+    // Without, candidates for different areas are rejected as ambiguous
+    fn helmert(&self, source: &Id, target: &Id) -> Result<Option<String>, Error> {
+        let mut statement = self.db()?.prepare(
+            "SELECT h.method_code, h.tx, h.ty, h.tz, h.translation_uom_code,
+                    h.rx, h.ry, h.rz, h.rotation_uom_code,
+                    h.scale_difference, h.scale_difference_uom_code,
+                    h.auth_name, h.code,
+                    x.west_lon, x.south_lat, x.east_lon, x.north_lat
+             FROM helmert_transformation h
+             JOIN geodetic_crs s
+             ON s.auth_name = h.source_crs_auth_name AND s.code = h.source_crs_code
+             JOIN geodetic_crs t
+             ON t.auth_name = h.target_crs_auth_name AND t.code = h.target_crs_code
+             LEFT JOIN usage u
+             ON u.object_table_name = 'helmert_transformation'
+             AND u.object_auth_name = h.auth_name AND u.object_code = h.code
+             LEFT JOIN extent x
+             ON x.auth_name = u.extent_auth_name AND x.code = u.extent_code
+             WHERE s.datum_auth_name = ?1 AND s.datum_code = ?2
+             AND t.datum_auth_name = ?3 AND t.datum_code = ?4
+             AND h.deprecated = 0
+             ORDER BY h.accuracy IS NULL, h.accuracy",
+        )?;
+
+        let mut candidates = Vec::new();
+        for (inverted, (from, to)) in [(false, (source, target)), (true, (target, source))] {
+            let rows = statement.query_map([&from.0, &from.1, &to.0, &to.1], |r| {
+                let helmert = Helmert {
+                    method: r.get(0)?,
+                    translation: [r.get(1)?, r.get(2)?, r.get(3)?],
+                    translation_uom: r.get(4)?,
+                    rotation: [r.get(5)?, r.get(6)?, r.get(7)?],
+                    rotation_uom: r.get(8)?,
+                    scale: r.get(9)?,
+                    scale_uom: r.get(10)?,
+                };
+                let id: Id = (r.get(11)?, r.get(12)?);
+                let extent: [Option<f64>; 4] = [r.get(13)?, r.get(14)?, r.get(15)?, r.get(16)?];
+                Ok((id, Extent::new(extent), inverted, helmert))
+            })?;
+            for row in rows {
+                candidates.push(row?);
+            }
+        }
+
+        // The candidates are ordered by accuracy, so the first one of the
+        // smallest area wins
+        let chosen = match self.area {
+            Some(area) => candidates
+                .iter()
+                .filter(|(_, extent, _, _)| extent.contains(&area))
+                .min_by(|a, b| a.1.size().total_cmp(&b.1.size())),
+            None => {
+                let ambiguous = candidates
+                    .iter()
+                    .any(|a| candidates.iter().any(|b| a.0 != b.0 && a.1 != b.1));
+                if ambiguous {
+                    return Err(Error::Invalid(format!(
+                        "{}:{} to {}:{}: Datum shifts for several areas. Set an area of interest",
+                        source.0, source.1, target.0, target.1
+                    )));
+                }
+                candidates.first()
+            }
+        };
+
+        let Some((_, _, inverted, helmert)) = chosen else {
+            return Ok(None);
+        };
+        let definition = helmert.definition()?;
+        Ok(Some(if *inverted {
+            format!("inv {definition}")
+        } else {
+            definition
+        }))
+    }
+}
+
+// An `authority:code` pair
+type Id = (String, String);
+
+// The parts of a CRS definition needed for building pipelines
+struct CrsDefinition {
+    datum: Id,
+    // PROJ ellipsoid parameters
+    ellps: String,
+    // Input and output adaptors, handling axis order and angular units
+    axes: (&'static str, &'static str),
+    // The PROJ step from geographic coordinates to the CRS coordinates,
+    // for projected and geocentric CRSs
+    projection: Option<String>,
+}
+
+// The area of use of a transformation: West, south, east and north bounds,
+// in degrees. Areas crossing the antimeridian have east < west
+#[derive(Debug, PartialEq)]
+struct Extent([f64; 4]);
+
+impl Extent {
+    // Transformations without an area of use are taken to be valid everywhere
+    fn new(bounds: [Option<f64>; 4]) -> Extent {
+        match bounds {
+            [Some(w), Some(s), Some(e), Some(n)] => Extent([w, s, e, n]),
+            _ => Extent([-180., -90., 180., 90.]),
+        }
+    }
+
+    fn width(&self) -> f64 {
+        let [w, _, e, _] = self.0;
+        if e < w {
+            e - w + 360.
+        } else {
+            e - w
+        }
+    }
+
+    // Size in square degrees. Good enough for finding the smallest area
+    fn size(&self) -> f64 {
+        self.width() * (self.0[3] - self.0[1])
+    }
+
+    // Does the extent cover the area `[west, south, east, north]`?
+    fn contains(&self, area: &[f64; 4]) -> bool {
+        let [w, s, _, n] = self.0;
+        let offset = |lon: f64| (lon - w).rem_euclid(360.);
+        let (west, east) = (offset(area[0]), offset(area[2]));
+        let longitudes = self.width() >= 360. || (west <= east && east <= self.width());
+        longitudes && s <= area[1] && area[3] <= n
+    }
+}
+
+// A row of the helmert_transformation table
+struct Helmert {
+    method: String,
+    translation: [f64; 3],
+    translation_uom: String,
+    rotation: [Option<f64>; 3],
+    rotation_uom: Option<String>,
+    scale: Option<f64>,
+    scale_uom: Option<String>,
+}
+
+impl Helmert {
+    // The PROJ definition of the Helmert transformation
+    fn definition(&self) -> Result<String, Error> {
+        let convention = match self.method.as_str() {
+            "9603" | "1031" => None,
+            "9606" | "1033" => Some("position_vector"),
+            "9607" | "1032" => Some("coordinate_frame"),
+            method => return Err(Error::Unsupported(format!("Helmert method {method}"))),
+        };
+        if self.translation_uom != METRE {
+            return Err(Error::Unsupported("Helmert translation units".to_string()));
+        }
+
+        let [x, y, z] = self.translation;
+        let mut definition = format!("proj=helmert x={x} y={y} z={z}");
+        let Some(convention) = convention else {
+            return Ok(definition);
+        };
+
+        let supported = self.rotation_uom.as_deref() == Some(ARC_SECOND)
+            && self.scale_uom.as_deref() == Some(PPM);
+        let ([Some(rx), Some(ry), Some(rz)], Some(s)) = (self.rotation, self.scale) else {
+            return Err(Error::Invalid("Incomplete Helmert parameters".to_string()));
+        };
+        if !supported {
+            return Err(Error::Unsupported(
+                "Helmert rotation or scale units".to_string(),
+            ));
+        }
+        definition += &format!(" rx={rx} ry={ry} rz={rz} s={s} convention={convention}");
+        Ok(definition)
+    }
+}
+
+// EPSG unit of measure codes
+const METRE: &str = "9001";
+const DEGREE: &str = "9102";
+const ARC_SECOND: &str = "9104";
+const PPM: &str = "9202";
+
+// EPSG prime meridian code
+const GREENWICH: &str = "8901";
+
+// A conversion method: EPSG method code, PROJ operator name, and the EPSG
+// parameter codes corresponding to the PROJ parameters
+type Method = (
+    &'static str,
+    &'static str,
+    &'static [(&'static str, &'static str)],
+);
+
+// The supported EPSG conversion methods
+#[rustfmt::skip]
+const METHODS: [Method; 4] = [
+    // Transverse Mercator
+    ("9807", "tmerc", &[("8801", "lat_0"), ("8802", "lon_0"), ("8805", "k"), ("8806", "x_0"), ("8807", "y_0")]),
+    // Lambert Conic Conformal (1SP)
+    ("9801", "lcc",   &[("8801", "lat_1"), ("8801", "lat_0"), ("8802", "lon_0"), ("8805", "k"), ("8806", "x_0"), ("8807", "y_0")]),
+    // Lambert Conic Conformal (2SP)
+    ("9802", "lcc",   &[("8823", "lat_1"), ("8824", "lat_2"), ("8821", "lat_0"), ("8822", "lon_0"), ("8826", "x_0"), ("8827", "y_0")]),
+    // Mercator (variant A)
+    ("9804", "merc",  &[("8802", "lon_0"), ("8805", "k"), ("8806", "x_0"), ("8807", "y_0")]),
+];
+
+// Convert a conversion parameter value to the units expected by PROJ:
+// Degrees, metres, and unity
+fn to_proj_units(value: f64, uom: &str) -> Result<f64, Error> {
+    Ok(match uom {
+        // metre, degree, degree (supplier to define representation), unity
+        "9001" | "9102" | "9122" | "9201" => value,
+        // foot, US survey foot
+        "9002" => value * 0.3048,
+        "9003" => value * 1200. / 3937.,
+        // radian
+        "9101" => value.to_degrees(),
+        // sexagesimal DMS, i.e. DDD.MMSSsss
+        "9110" => {
+            // Split the parts in integer units of 1e-5 arc seconds, since
+            // truncating the float parts directly is thrown by representation
+            // errors, e.g. 45.29 being stored as 45.28999...
+            let sign = value.signum();
+            let value = (value.abs() * 1e9).round() as i64;
+            let degrees = (value / 1_000_000_000) as f64;
+            let minutes = (value / 10_000_000 % 100) as f64;
+            let seconds = (value % 10_000_000) as f64 / 1e5;
+            sign * (degrees + minutes / 60. + seconds / 3600.)
+        }
+        _ => return Err(Error::Unsupported(format!("Unit of measure {uom}"))),
+    })
+}
+
+impl Context for ProjDb {
+    fn new() -> ProjDb {
+        let plain = Plain::new();
+        let db = plain.find_file("proj.db").and_then(|path| {
+            Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).ok()
+        });
+        ProjDb {
+            plain,
+            db,
+            area: None,
+        }
+    }
+
+    fn op(&mut self, definition: &str) -> Result<OpHandle, Error> {
+        self.plain.op(definition)
+    }
+
+    /// Instantiate the operation between two CRSs, using [`ProjDb::definition`]
+    fn op_from_crs(&mut self, source: &str, target: &str) -> Result<OpHandle, Error> {
+        let definition = self.definition(source, target)?;
+        self.plain.op(&definition)
+    }
+
+    fn apply(
+        &self,
+        op: OpHandle,
+        direction: Direction,
+        operands: &mut dyn CoordinateSet,
+    ) -> Result<usize, Error> {
+        self.plain.apply(op, direction, operands)
+    }
+
+    fn steps(&self, op: OpHandle) -> Result<&Vec<String>, Error> {
+        self.plain.steps(op)
+    }
+
+    fn params(&self, op: OpHandle, index: usize) -> Result<ParsedParameters, Error> {
+        self.plain.params(op, index)
+    }
+
+    fn globals(&self) -> BTreeMap<String, String> {
+        self.plain.globals()
+    }
+
+    fn register_op(&mut self, name: &str, constructor: OpConstructor) {
+        self.plain.register_op(name, constructor)
+    }
+
+    fn get_op(&self, name: &str) -> Result<OpConstructor, Error> {
+        self.plain.get_op(name)
+    }
+
+    fn register_resource(&mut self, name: &str, definition: &str) {
+        self.plain.register_resource(name, definition)
+    }
+
+    fn get_resource(&self, name: &str) -> Result<String, Error> {
+        self.plain.get_resource(name)
+    }
+
+    fn get_blob(&self, name: &str) -> Result<Vec<u8>, Error> {
+        self.plain.get_blob(name)
+    }
+
+    fn get_grid(&self, name: &str) -> Result<Arc<dyn Grid>, Error> {
+        self.plain.get_grid(name)
+    }
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // geodesy/db/proj.db is a tiny extract of the real thing, holding just
+    // the tables, columns and rows needed for these tests. The areas of use
+    // of the transformations are simplified stand-ins for the EPSG ones, and
+    // the deprecated transformation TEST:1 exists only to be ignored
+    fn ctx() -> Result<ProjDb, Error> {
+        ProjDb::open(Path::new("geodesy/db/proj.db"))
+    }
+
+    // Denmark, where only the general European ED50 transformation applies
+    fn denmark() -> Result<ProjDb, Error> {
+        Ok(ctx()?.with_area_of_interest(8., 54.5, 15.5, 58.))
+    }
+
+    #[test]
+    fn definition() -> Result<(), Error> {
+        let ctx = denmark()?;
+
+        // Same datum: No datum shift
+        assert_eq!(
+            ctx.definition("EPSG:4619", "EPSG:3006")?,
+            "geo:in | tmerc lat_0=0 lon_0=15 k_0=0.9996 x_0=500000 y_0=0 ellps=6378137,298.257222101 | neu:out"
+        );
+        assert_eq!(
+            ctx.definition("EPSG:4326", "EPSG:4326")?,
+            "geo:in | geo:out"
+        );
+
+        // Datum shift, and geocentric coordinates
+        assert_eq!(
+            ctx.definition("EPSG:4230", "EPSG:4978")?,
+            "geo:in | cart ellps=6378388,297 | helmert x=-87 y=-98 z=-121 | cart inv ellps=6378137,298.257223563 | cart ellps=6378137,298.257223563"
+        );
+
+        assert!(matches!(
+            ctx.definition("EPSG:4326", "EPSG:1"),
+            Err(Error::NotFound(_, _))
+        ));
+        assert!(matches!(
+            ctx.definition("4326", "EPSG:4326"),
+            Err(Error::BadParam(_, _))
+        ));
+        // No datum shift between SWEREF99 and WGS 84 in the test database
+        assert!(ctx.definition("EPSG:4326", "EPSG:3006").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn transformations() -> Result<(), Error> {
+        let mut ctx = denmark()?;
+
+        // ETRS89 to UTM zone 32 is the plain "utm zone=32"
+        let op = ctx.op(&ctx.definition("EPSG:4258", "EPSG:25832")?)?;
+        let utm = ctx.op("geo:in | utm zone=32")?;
+        let mut data = [Coor4D::raw(55., 12., 0., 0.)];
+        let mut expected = data;
+        ctx.apply(op, Fwd, &mut data)?;
+        ctx.apply(utm, Fwd, &mut expected)?;
+        assert!(data[0].hypot2(&expected[0]) < 1e-6);
+
+        // ...and WGS 84 to ETRS89 is a null transformation, save for the
+        // sub-millimetre effect of the slightly different ellipsoids
+        let op = ctx.op(&ctx.definition("EPSG:4326", "EPSG:25832")?)?;
+        let mut data = [Coor4D::raw(55., 12., 0., 0.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!(data[0].hypot2(&expected[0]) < 1e-3);
+        ctx.apply(op, Inv, &mut data)?;
+        assert!(data[0].hypot2(&Coor4D::raw(55., 12., 0., 0.)) < 1e-12);
+
+        // ED50 to ETRS89 via WGS 84, and the reverse direction
+        let op = ctx.op(&ctx.definition("EPSG:23032", "EPSG:25832")?)?;
+        let back = ctx.op(&ctx.definition("EPSG:25832", "EPSG:23032")?)?;
+        let explicit = ctx.op(
+            "inv utm zone=32 ellps=intl | cart ellps=intl | helmert x=-87 y=-98 z=-121 | inv cart | utm zone=32",
+        )?;
+        let mut data = [Coor4D::raw(700000., 6100000., 0., 0.)];
+        let mut expected = data;
+        ctx.apply(op, Fwd, &mut data)?;
+        ctx.apply(explicit, Fwd, &mut expected)?;
+        assert!(data[0].hypot2(&expected[0]) < 1e-6);
+        ctx.apply(back, Fwd, &mut data)?;
+        assert!(data[0].hypot2(&Coor4D::raw(700000., 6100000., 0., 0.)) < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn areas() -> Result<(), Error> {
+        // ED50 to WGS 84 has variants for Europe, France and the North Sea
+        let helmert = |ctx: ProjDb| {
+            ctx.helmert(
+                &("EPSG".into(), "6230".into()),
+                &("EPSG".into(), "6326".into()),
+            )
+        };
+        assert!(matches!(helmert(ctx()?), Err(Error::Invalid(_))));
+        assert!(helmert(denmark()?)?
+            .unwrap()
+            .starts_with("proj=helmert x=-87 y=-98 z=-121"));
+
+        // The smallest area covering the area of interest wins
+        let paris = ctx()?.with_area_of_interest(2.2, 48.8, 2.5, 48.9);
+        assert!(helmert(paris)?
+            .unwrap()
+            .starts_with("proj=helmert x=-84 y=-97 z=-117"));
+        let north_sea = ctx()?.with_area_of_interest(1.5, 57., 2., 58.);
+        assert_eq!(
+            helmert(north_sea)?.unwrap(),
+            "proj=helmert x=-89.5 y=-93.8 z=-123.1 rx=0 ry=0 rz=-0.156 s=1.2 convention=position_vector"
+        );
+
+        // Nothing covers America
+        let america = ctx()?.with_area_of_interest(-100., 40., -99., 41.);
+        assert!(helmert(america)?.is_none());
+        let america = ctx()?.with_area_of_interest(-100., 40., -99., 41.);
+        assert!(america.definition("EPSG:4230", "EPSG:4326").is_err());
+
+        // Pairs with a single area of use need no area of interest
+        assert!(ctx()?.definition("EPSG:4258", "EPSG:4326").is_ok());
+
+        // Areas crossing the antimeridian
+        let pacific = Extent([170., -50., -170., 0.]);
+        assert!(pacific.contains(&[175., -20., -175., -10.]));
+        assert!(pacific.contains(&[-179., -20., -175., -10.]));
+        assert!(!pacific.contains(&[160., -20., 175., -10.]));
+        assert_eq!(pacific.size(), 20. * 50.);
+        assert!(Extent::new([None; 4]).contains(&[-100., 40., -99., 41.]));
+        Ok(())
+    }
+
+    #[test]
+    fn op_from_crs() -> Result<(), Error> {
+        let mut ctx = ctx()?;
+        let op = ctx.op_from_crs("EPSG:4619", "EPSG:3006")?;
+        let mut data = [Coor4D::raw(59., 15., 0., 0.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][1] - 500000.).abs() < 1e-6);
+        ctx.apply(op, Inv, &mut data)?;
+        assert!(data[0].hypot2(&Coor4D::raw(59., 15., 0., 0.)) < 1e-12);
+        assert!(ctx.op_from_crs("EPSG:4326", "EPSG:1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn units() -> Result<(), Error> {
+        assert_eq!(to_proj_units(1.0, "9001")?, 1.0);
+        assert!((to_proj_units(1.0, "9003")? - 0.3048006096).abs() < 1e-10);
+        assert!((to_proj_units(-12.3030, "9110")? - -12.5083333333).abs() < 1e-9);
+        assert!((to_proj_units(45.29, "9110")? - 45.4833333333).abs() < 1e-9);
+        assert!((to_proj_units(0.57, "9110")? - 0.95).abs() < 1e-12);
+        assert!((to_proj_units(33.58, "9110")? - 33.9666666667).abs() < 1e-9);
+        assert!((to_proj_units(10.2030405, "9110")? - 10.3417791667).abs() < 1e-9);
+        assert!(to_proj_units(1.0, "9999").is_err());
+
+        // Without a database, nothing can be resolved
+        let ctx = ProjDb {
+            plain: Plain::new(),
+            db: None,
+            area: None,
+        };
+        assert!(ctx.definition("EPSG:4326", "EPSG:4326").is_err());
+        Ok(())
+    }
+}
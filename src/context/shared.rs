@@ -0,0 +1,381 @@
+use crate::authoring::*;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+// ----- T H E   S H A R E D   C O N T E X T -------------------------------------------
+
+/// A thread safe context provider, with the same resource, grid, and macro
+/// handling as [`Plain`].
+///
+/// `Shared` is `Send + Sync`, so a single instance may be shared between
+/// threads, e.g. behind an `Arc`. Operators and resources are registered
+/// through a shared reference. The operator instantiations are kept at fixed
+/// locations, given by their [`OpHandle`], so `apply` takes no locks. Only
+/// the registration and instantiation of operators, which may need to read
+/// resources and grids, lock the underlying [`Plain`] context.
+///
+/// Operators are removed using [`Shared::remove_op`]. Since [`Context::steps`]
+/// hands out references into the operator, removal requires exclusive access
+/// to the context. The slot of a removed operator is reused by the next
+/// instantiation.
+#[derive(Debug)]
+pub struct Shared {
+    plain: RwLock<Plain>,
+    operators: OpStore,
+}
+
+const BAD_ID_MESSAGE: Error = Error::General("Shared: Unknown operator id");
+
+impl Shared {
+    /// Instantiate an operator. Equivalent to [`Context::op`], but usable
+    /// through a shared reference
+    pub fn op(&self, definition: &str) -> Result<OpHandle, Error> {
+        // It may be a PROJ string, so we filter it through the PROJ parser
+        let definition = parse_proj(definition)?;
+        let op = Op::new(&definition, self)?;
+        Ok(self.operators.push(op))
+    }
+
+    /// Remove the operator `op`, releasing its resources. Returns `false`
+    /// if `op` is unknown, e.g. because it was already removed
+    pub fn remove_op(&mut self, op: OpHandle) -> bool {
+        self.operators.remove(op).is_some()
+    }
+
+    /// Register a user defined operator. Equivalent to [`Context::register_op`],
+    /// but usable through a shared reference
+    pub fn register_op(&self, name: &str, constructor: OpConstructor) {
+        self.plain.write().unwrap().register_op(name, constructor);
+    }
+
+    /// Register a user defined resource. Equivalent to
+    /// [`Context::register_resource`], but usable through a shared reference
+    pub fn register_resource(&self, name: &str, definition: &str) {
+        self.plain
+            .write()
+            .unwrap()
+            .register_resource(name, definition);
+    }
+
+    /// See [`Plain::add_path`]
+    pub fn add_path(&self, path: impl Into<std::path::PathBuf>) {
+        self.plain.write().unwrap().add_path(path);
+    }
+
+    /// See [`Plain::set_grid_size_threshold`]
+    pub fn set_grid_size_threshold(&self, threshold: u64) {
+        self.plain
+            .write()
+            .unwrap()
+            .set_grid_size_threshold(threshold);
+    }
+
+    /// Apply the operator `op` to `operands`, split into chunks handled by
+    /// separate threads. If `threads` is 0, the number of threads is the
+    /// available parallelism of the system.
+    /// Returns the number of successfully transformed coordinates.
+    pub fn apply_parallel<T>(
+        &self,
+        op: OpHandle,
+        direction: Direction,
+        operands: &mut [T],
+        threads: usize,
+    ) -> Result<usize, Error>
+    where
+        T: Send,
+        for<'a> &'a mut [T]: CoordinateSet,
+    {
+        let op = self.operators.get(op).ok_or(BAD_ID_MESSAGE)?;
+        let threads = match threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        if operands.is_empty() {
+            return Ok(0);
+        }
+        let chunk_size = (operands.len() + threads - 1) / threads;
+        let forward = direction == Direction::Fwd;
+
+        let successes = std::thread::scope(|scope| {
+            let workers: Vec<_> = operands
+                .chunks_mut(chunk_size)
+                .map(|mut chunk| {
+                    scope.spawn(move || {
+                        let direction = if forward { Fwd } else { Inv };
+                        op.apply(self, &mut chunk, direction)
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).sum()
+        });
+        Ok(successes)
+    }
+}
+
+impl Context for Shared {
+    fn new() -> Shared {
+        Shared {
+            plain: RwLock::new(Plain::new()),
+            operators: OpStore::default(),
+        }
+    }
+
+    fn op(&mut self, definition: &str) -> Result<OpHandle, Error> {
+        Shared::op(self, definition)
+    }
+
+    fn apply(
+        &self,
+        op: OpHandle,
+        direction: Direction,
+        operands: &mut dyn CoordinateSet,
+    ) -> Result<usize, Error> {
+        let op = self.operators.get(op).ok_or(BAD_ID_MESSAGE)?;
+        Ok(op.apply(self, operands, direction))
+    }
+
+    fn steps(&self, op: OpHandle) -> Result<&Vec<String>, Error> {
+        let op = self.operators.get(op).ok_or(BAD_ID_MESSAGE)?;
+        Ok(&op.descriptor.steps)
+    }
+
+    fn params(&self, op: OpHandle, index: usize) -> Result<ParsedParameters, Error> {
+        let op = self.operators.get(op).ok_or(BAD_ID_MESSAGE)?;
+        // Leaf level?
+        if op.steps.is_empty() {
+            if index > 0 {
+                return Err(Error::General("Shared: Bad step index"));
+            }
+            return Ok(op.params.clone());
+        }
+
+        // Not leaf level
+        if index >= op.steps.len() {
+            return Err(Error::General("Shared: Bad step index"));
+        }
+        Ok(op.steps[index].params.clone())
+    }
+
+    fn globals(&self) -> BTreeMap<String, String> {
+        self.plain.read().unwrap().globals()
+    }
+
+    fn register_op(&mut self, name: &str, constructor: OpConstructor) {
+        Shared::register_op(self, name, constructor)
+    }
+
+    fn get_op(&self, name: &str) -> Result<OpConstructor, Error> {
+        self.plain.read().unwrap().get_op(name)
+    }
+
+    fn register_resource(&mut self, name: &str, definition: &str) {
+        Shared::register_resource(self, name, definition)
+    }
+
+    fn get_resource(&self, name: &str) -> Result<String, Error> {
+        self.plain.read().unwrap().get_resource(name)
+    }
+
+    fn get_blob(&self, name: &str) -> Result<Vec<u8>, Error> {
+        self.plain.read().unwrap().get_blob(name)
+    }
+
+    fn get_grid(&self, name: &str) -> Result<Arc<dyn Grid>, Error> {
+        self.plain.read().unwrap().get_grid(name)
+    }
+}
+
+impl Default for Shared {
+    fn default() -> Shared {
+        Shared::new()
+    }
+}
+
+// ----- T H E   O P E R A T O R   S T O R E -------------------------------------------
+
+// Segment k of the store holds 2^k operators, so 32 segments is plenty
+const SEGMENTS: usize = 32;
+
+// Storage of operator instantiations. Segments are allocated on demand, and
+// never moved nor freed while the store lives, so references to stored
+// operators remain valid across later insertions. Insertions are serialized
+// by a mutex, and fill the slots of removed operators before extending the
+// store. Removal needs exclusive access, so no references into an emptied
+// slot can be alive. The handle of an operator carries its slot number, so
+// look-ups need no locks. Each slot also holds the handle, so handles of
+// removed operators are not mistaken for those of their successors.
+#[derive(Debug, Default)]
+struct OpStore {
+    segments: [OnceLock<Segment>; SEGMENTS],
+    slots: Mutex<Slots>,
+}
+
+// A segment of slots, each holding an operator and its handle
+type Segment = Box<[OnceLock<(OpHandle, Op)>]>;
+
+// Number of slots in use or freed, and the freed ones, available for reuse
+#[derive(Debug, Default)]
+struct Slots {
+    len: usize,
+    free: Vec<usize>,
+}
+
+impl OpStore {
+    // Segment and offset of slot number `index`
+    fn position(index: usize) -> (usize, usize) {
+        let k = index + 1;
+        let segment = (usize::BITS - 1 - k.leading_zeros()) as usize;
+        (segment, k - (1 << segment))
+    }
+
+    fn slot(&self, index: usize) -> Option<&(OpHandle, Op)> {
+        // Handles from elsewhere may carry any number
+        if index == usize::MAX {
+            return None;
+        }
+        let (segment, offset) = Self::position(index);
+        self.segments.get(segment)?.get()?.get(offset)?.get()
+    }
+
+    fn push(&self, op: Op) -> OpHandle {
+        let mut slots = self.slots.lock().unwrap();
+        let index = slots.free.pop().unwrap_or_else(|| {
+            slots.len += 1;
+            slots.len - 1
+        });
+        let (segment, offset) = Self::position(index);
+        let segment = self.segments[segment]
+            .get_or_init(|| (0..1_usize << segment).map(|_| OnceLock::new()).collect());
+        // The slot is empty, since insertions are serialized
+        let id = OpHandle::with_slot(index);
+        let _ = segment[offset].set((id, op));
+        id
+    }
+
+    fn get(&self, id: OpHandle) -> Option<&Op> {
+        let (handle, op) = self.slot(id.slot())?;
+        (*handle == id).then_some(op)
+    }
+
+    fn remove(&mut self, id: OpHandle) -> Option<Op> {
+        self.get(id)?;
+        let index = id.slot();
+        let (segment, offset) = Self::position(index);
+        let (_, op) = self.segments[segment].get_mut()?[offset].take()?;
+        self.slots.get_mut().unwrap().free.push(index);
+        Some(op)
+    }
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Shared>();
+    }
+
+    #[test]
+    fn op_store() -> Result<(), Error> {
+        assert_eq!(OpStore::position(0), (0, 0));
+        assert_eq!(OpStore::position(1), (1, 0));
+        assert_eq!(OpStore::position(2), (1, 1));
+        assert_eq!(OpStore::position(3), (2, 0));
+        assert_eq!(OpStore::position(6), (2, 3));
+        assert_eq!(OpStore::position(7), (3, 0));
+
+        // Removed operators are gone, and their slots are reused
+        let mut ctx = Shared::new();
+        let ops: Vec<_> = (0..5).map(|_| ctx.op("addone").unwrap()).collect();
+        assert!(ctx.remove_op(ops[1]));
+        assert!(ctx.remove_op(ops[3]));
+        assert!(!ctx.remove_op(ops[3]));
+        assert!(ctx.steps(ops[3]).is_err());
+        assert_eq!(ctx.operators.slots.lock().unwrap().free, [1, 3]);
+
+        let op = ctx.op("addone | addone")?;
+        assert_eq!(ctx.operators.slots.lock().unwrap().len, 5);
+        assert_eq!(op.slot(), 3);
+        assert_eq!(ctx.steps(op)?.len(), 2);
+
+        // The handle of the removed operator does not reach its successor
+        assert_eq!(ops[3].slot(), 3);
+        assert!(ctx.steps(ops[3]).is_err());
+        for op in [ops[0], ops[2], ops[4]] {
+            let mut data = [Coor2D::origin()];
+            ctx.apply(op, Fwd, &mut data)?;
+            assert_eq!(data[0][0], 1.);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn shared() -> Result<(), Error> {
+        let ctx = Arc::new(Shared::new());
+
+        // Registration and instantiation through shared references
+        ctx.register_resource("foo:bar", "addone | addone");
+        let first = ctx.op("foo:bar")?;
+
+        // Instantiate and apply operators from several threads at once
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let ctx = ctx.clone();
+                std::thread::spawn(move || -> Result<(), Error> {
+                    let mut ops = Vec::new();
+                    for _ in 0..10 {
+                        ops.push(ctx.op("utm zone=32")?);
+                    }
+                    let mut data = [Coor2D::geo(55., 12.)];
+                    ctx.apply(first, Fwd, &mut data)?;
+                    assert!((data[0][0] - 12f64.to_radians() - 2.).abs() < 1e-12);
+                    for op in ops {
+                        let mut data = [Coor2D::geo(55., 12. + i as f64)];
+                        ctx.apply(op, Fwd, &mut data)?;
+                        ctx.apply(op, Inv, &mut data)?;
+                        assert!((data[0][0].to_degrees() - (12. + i as f64)).abs() < 1e-9);
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap()?;
+        }
+
+        assert_eq!(ctx.steps(first)?.len(), 2);
+        assert!(ctx
+            .apply(OpHandle::new(), Fwd, &mut [Coor2D::origin()])
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn apply_parallel() -> Result<(), Error> {
+        let ctx = Shared::new();
+        let op = ctx.op("geo:in | utm zone=32")?;
+
+        let mut data: Vec<Coor4D> = (0..1000)
+            .map(|i| Coor4D::raw(55. + i as f64 / 1000., 12., 0., 0.))
+            .collect();
+        let mut expected = data.clone();
+        ctx.apply(op, Fwd, &mut expected)?;
+
+        for threads in [0, 1, 3, 2000] {
+            let mut copy = data.clone();
+            assert_eq!(ctx.apply_parallel(op, Fwd, &mut copy, threads)?, 1000);
+            assert_eq!(copy, expected);
+        }
+
+        ctx.apply_parallel(op, Fwd, &mut data, 4)?;
+        ctx.apply_parallel(op, Inv, &mut data, 4)?;
+        assert!((data[999][0] - 55.999).abs() < 1e-9);
+
+        let mut empty: Vec<Coor2D> = Vec::new();
+        assert_eq!(ctx.apply_parallel(op, Fwd, &mut empty, 4)?, 0);
+        Ok(())
+    }
+}
@@ -93,6 +93,23 @@ pub enum Crs {
     RegisterItem(String, String),
 }
 
+impl std::str::FromStr for Crs {
+    type Err = Error;
+
+    /// Parse a register item identifier, e.g. `EPSG:4326`
+    fn from_str(id: &str) -> Result<Crs, Error> {
+        match id.trim().split_once(':') {
+            Some((register, item)) if !register.is_empty() && !item.is_empty() => {
+                Ok(Crs::RegisterItem(register.to_string(), item.to_string()))
+            }
+            _ => Err(Error::BadParam(
+                "needing register:item format".to_string(),
+                id.to_string(),
+            )),
+        }
+    }
+}
+
 // ----- Interface: Coordinate Metadata ---------------------------------------
 
 /// The ISO-19111 Coordinate Metadata gamut includes an optional
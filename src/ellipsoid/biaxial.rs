@@ -62,7 +62,9 @@ impl Ellipsoid {
         if a_and_rf.len() == 2_usize {
             if let Ok(a) = a_and_rf[0].trim().parse::<f64>() {
                 if let Ok(rf) = a_and_rf[1].trim().parse::<f64>() {
-                    return Ok(Ellipsoid::new(a, 1. / rf));
+                    // Zero reciproque flattening indicates a sphere, as above
+                    let f = if rf != 0.0 { 1.0 / rf } else { rf };
+                    return Ok(Ellipsoid::new(a, f));
                 }
             }
         }
@@ -98,6 +100,11 @@ mod tests {
         assert_eq!(ellps.semimajor_axis(), 6378137.0);
         assert_eq!(ellps.flattening(), 1. / 298.25);
 
+        // Zero reciproque flattening: A sphere
+        let ellps = Ellipsoid::named("6400000, 0")?;
+        assert_eq!(ellps.semimajor_axis(), 6400000.0);
+        assert_eq!(ellps.flattening(), 0.);
+
         let ellps = Ellipsoid::named("GRS80")?;
         assert_eq!(ellps.semimajor_axis(), 6378137.0);
         assert_eq!(ellps.flattening(), 1. / 298.257_222_100_882_7);
@@ -201,6 +201,56 @@ pub trait Geodesics: EllipsoidBase {
     fn distance<G: CoordinateTuple>(&self, from: &G, to: &G) -> f64 {
         self.geodesic_inv(from, to)[2]
     }
+
+    /// Densify a line feature, given as a sequence of (longitude, latitude)
+    /// vertices in radians, by inserting intermediate points along the geodesics
+    /// between consecutive vertices, such that no segment is longer than
+    /// `max_distance` meters. The third and fourth coordinates of the inserted
+    /// points are interpolated linearly.
+    ///
+    /// Projecting the densified line, rather than the original, prevents long
+    /// segments from being rendered as straight chords in the projected domain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Densify the route Copenhagen-Paris to 100 km segments
+    /// use geodesy::prelude::*;
+    /// let ellps = Ellipsoid::named("GRS80").unwrap();
+    /// let route = [Coor4D::geo(55., 12., 0., 0.), Coor4D::geo(49., 2., 0., 0.)];
+    /// let dense = ellps.geodesic_densify(&route, 100_000.);
+    /// assert_eq!(dense.len(), 11);
+    /// ```
+    #[must_use]
+    fn geodesic_densify(&self, line: &dyn CoordinateSet, max_distance: f64) -> Vec<Coor4D> {
+        let mut result = Vec::new();
+        if line.is_empty() {
+            return result;
+        }
+
+        let mut from = line.get_coord(0);
+        result.push(from);
+        for i in 1..line.len() {
+            let to = line.get_coord(i);
+            let geodesic = self.geodesic_inv(&from, &to);
+            let (azimuth, distance) = (geodesic[0], geodesic[2]);
+
+            // Skip densification on non-convergence, or if unnecessary
+            if geodesic[3] < 990. && max_distance > 0. && distance > max_distance {
+                let n = (distance / max_distance).ceil();
+                for j in 1..n as usize {
+                    let fraction = j as f64 / n;
+                    let mut point = self.geodesic_fwd(&from, azimuth, fraction * distance);
+                    point[2] = from[2] + fraction * (to[2] - from[2]);
+                    point[3] = from[3] + fraction * (to[3] - from[3]);
+                    result.push(point);
+                }
+            }
+            result.push(to);
+            from = to;
+        }
+        result
+    }
 }
 
 // ----- Tests ---------------------------------------------------------------------
@@ -245,4 +295,36 @@ mod tests {
         assert!((b[1].to_degrees() - p2[1].to_degrees()).abs() < 1e-9);
         Ok(())
     }
+
+    #[test]
+    fn densify() -> Result<(), Error> {
+        let ellps = Ellipsoid::named("GRS80")?;
+
+        // Copenhagen-Paris-Rabat, with heights 0, 100, and 40 m
+        let line = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(49., 2., 100., 0.),
+            Coor4D::geo(34., -7., 40., 0.),
+        ];
+        let dense = ellps.geodesic_densify(&line, 250_000.);
+
+        // 4 segments for the first leg, 8 for the second
+        assert_eq!(dense.len(), 13);
+        assert_eq!(dense[0], line[0]);
+        assert_eq!(dense[4], line[1]);
+        assert_eq!(dense[12], line[2]);
+        for i in 1..dense.len() {
+            let d = ellps.distance(&dense[i - 1], &dense[i]);
+            assert!(d <= 250_000.);
+        }
+        assert!((dense[2][2] - 50.).abs() < 1e-9);
+        assert!((dense[8][2] - 70.).abs() < 1e-9);
+
+        // No densification needed
+        assert_eq!(ellps.geodesic_densify(&line, 5e6).len(), 3);
+        assert!(ellps
+            .geodesic_densify(&Vec::<Coor4D>::new(), 1e5)
+            .is_empty());
+        Ok(())
+    }
 }
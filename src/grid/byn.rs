@@ -0,0 +1,172 @@
+//! Reader for the Natural Resources Canada `.byn` grid format: An 80 byte
+//! header, followed by the grid values as 16 or 32 bit integers, in
+//! north-to-south, west-to-east scan order. The header gives the boundaries
+//! and spacing of the grid in seconds of arc, and the byte order of the file.
+use super::file_size;
+use crate::Error;
+
+pub(super) const HEADER_SIZE: usize = 80;
+
+// Header offsets
+const SOUTH: usize = 0;
+const NORTH: usize = 4;
+const WEST: usize = 8;
+const EAST: usize = 12;
+const DLAT: usize = 16;
+const DLON: usize = 18;
+const FACTOR: usize = 24;
+const SIZE_OF: usize = 32;
+const BYTE_ORDER: usize = 44;
+const SCALE: usize = 46;
+
+/// The storage details of the grid values of a BYN file
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BynLayout {
+    big_endian: bool,
+    factor: f64,
+    /// The size of each value, in bytes: 2 or 4
+    pub(super) size: usize,
+}
+
+impl BynLayout {
+    /// Decode a single grid value, replacing nodata with NaN. Nodata is
+    /// 32767 for 16 bit grids, and 9999 (after scaling) for 32 bit grids
+    pub(super) fn value(&self, bytes: &[u8]) -> f32 {
+        let mut b = [0_u8; 4];
+        b[..self.size].copy_from_slice(&bytes[..self.size]);
+        if self.big_endian {
+            b[..self.size].reverse();
+        }
+        if self.size == 2 {
+            return match i16::from_le_bytes([b[0], b[1]]) {
+                i16::MAX => f32::NAN,
+                value => (value as f64 / self.factor) as f32,
+            };
+        }
+        let value = i32::from_le_bytes(b) as f64 / self.factor;
+        if value == 9999. {
+            return f32::NAN;
+        }
+        value as f32
+    }
+}
+
+/// The plain header (in radians), the number of rows and columns, and the
+/// storage layout, from the first `HEADER_SIZE` bytes of a BYN file
+pub(super) fn byn_header(buf: &[u8]) -> Result<([f64; 7], usize, usize, BynLayout), Error> {
+    if buf.len() < HEADER_SIZE {
+        return Err(Error::Invalid("BYN: Incomplete header".to_string()));
+    }
+
+    // The byte order field is 0 for big endian, 1 for little endian
+    // files, so we can determine the byte order by inspecting it
+    let big_endian = match (buf[BYTE_ORDER], buf[BYTE_ORDER + 1]) {
+        (1, 0) => false,
+        (0, 0) => true,
+        _ => return Err(Error::Unsupported("Not a BYN file".to_string())),
+    };
+    let bytes = |i: usize, n: usize| {
+        let mut b = [0_u8; 8];
+        b[..n].copy_from_slice(&buf[i..i + n]);
+        if big_endian {
+            b[..n].reverse();
+        }
+        b
+    };
+    let i16_at = |i: usize| i16::from_le_bytes(bytes(i, 2)[..2].try_into().unwrap());
+    let i32_at = |i: usize| i32::from_le_bytes(bytes(i, 4)[..4].try_into().unwrap());
+    let f64_at = |i: usize| f64::from_le_bytes(bytes(i, 8));
+
+    // Boundaries and spacing are in seconds of arc, optionally scaled by 1000
+    let scale = if i16_at(SCALE) == 1 { 1000. } else { 1. };
+    let seconds = |value: f64| (value / scale / 3600.).to_radians();
+    let lat_s = seconds(i32_at(SOUTH) as f64);
+    let lat_n = seconds(i32_at(NORTH) as f64);
+    let lon_w = seconds(i32_at(WEST) as f64);
+    let lon_e = seconds(i32_at(EAST) as f64);
+    let dlat = seconds(i16_at(DLAT) as f64);
+    let dlon = seconds(i16_at(DLON) as f64);
+
+    if dlat <= 0. || dlon <= 0. || lat_n <= lat_s || lon_e <= lon_w {
+        return Err(Error::Invalid("BYN: Malformed header".to_string()));
+    }
+    let rows = ((lat_n - lat_s) / dlat + 1.5).floor() as usize;
+    let cols = ((lon_e - lon_w) / dlon + 1.5).floor() as usize;
+
+    // The stored values are the grid values multiplied by `factor`
+    let factor = f64_at(FACTOR);
+    let size = i16_at(SIZE_OF) as usize;
+    if factor <= 0. || (size != 2 && size != 4) {
+        return Err(Error::Invalid("BYN: Malformed header".to_string()));
+    }
+
+    let header = [lat_n, lat_s, lon_w, lon_e, dlat, dlon, 1.];
+    let layout = BynLayout {
+        big_endian,
+        factor,
+        size,
+    };
+    Ok((header, rows, cols, layout))
+}
+
+pub(super) fn byn_grid_reader(buf: &[u8]) -> Result<([f64; 7], Vec<f32>), Error> {
+    let (header, rows, cols, layout) = byn_header(buf)?;
+    let size = layout.size;
+    let Some(file_size) = file_size(HEADER_SIZE, size, rows, cols) else {
+        return Err(Error::Invalid("BYN: Malformed header".to_string()));
+    };
+    if buf.len() < file_size {
+        return Err(Error::Invalid("BYN: Incomplete grid".to_string()));
+    }
+
+    let grid = (0..rows * cols)
+        .map(|i| layout.value(&buf[HEADER_SIZE + size * i..]))
+        .collect();
+    Ok((header, grid))
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{BaseGrid, Grid};
+    use crate::prelude::*;
+
+    #[test]
+    fn byn() -> Result<(), Error> {
+        // The Gravsoft test geoid, as little endian 32 bit integers scaled
+        // by 1000, with a nodata value at the 54N 8E node
+        let buf = std::fs::read("geodesy/byn/test.byn")?;
+        let byn = BaseGrid::byn(&buf)?;
+        let gravsoft = BaseGrid::gravsoft(&std::fs::read("geodesy/geoid/test.geoid")?)?;
+        assert_eq!(byn.bands(), 1);
+
+        for c in [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(57.5, 15.25, 0., 0.),
+        ] {
+            let expected = gravsoft.at(&c, 0.).unwrap();
+            assert!((byn.at(&c, 0.).unwrap()[0] - expected[0]).abs() < 1e-5);
+        }
+
+        // Interpolation involving nodata nodes fails
+        let c = Coor4D::geo(54.5, 8.5, 0., 0.);
+        assert!(byn.at(&c, 0.).is_none());
+
+        assert!(BaseGrid::byn(&buf[0..100]).is_err());
+
+        // A corrupt header, claiming a grid of more than 2^64 nodes: Unscaled
+        // boundaries at the extremes of the i32 range, with 1" spacing
+        let mut corrupt = buf.clone();
+        for (offset, value) in [(0, i32::MIN), (4, i32::MAX), (8, i32::MIN), (12, i32::MAX)] {
+            corrupt[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        corrupt[16..20].copy_from_slice(&[1, 0, 1, 0]);
+        corrupt[46..48].copy_from_slice(&[0, 0]);
+        assert!(matches!(
+            BaseGrid::byn(&corrupt),
+            Err(Error::Invalid(message)) if message.contains("Malformed")
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+//! File backed grids: Rather than reading the entire grid into memory, the
+//! grid values are read from the file, on demand, when interpolating. This
+//! is useful for large (national or global) geoid models, when transforming
+//! only a handful of points.
+//!
+//! Only uncompressed binary formats with a fixed layout support this kind of
+//! random access. Currently these are the GTX and BYN geoid model formats.
+//!
+//! The values are read a full row at a time, into a small cache of recently
+//! used rows, shared between all users of the grid. Hence, consecutive
+//! interpolations in the same area only read from the file once, and threads
+//! only need exclusive access to the file when reading rows not in the cache.
+use super::{byn, file_size, gtx, BaseGrid, Grid, GridInfo, Interpolation};
+use crate::{coord::Coor4D, Error};
+use log::warn;
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+    sync::{Mutex, RwLock},
+};
+
+// The number of rows held in the row cache
+const ROW_CACHE_SIZE: usize = 64;
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    // 32 bit big endian floats, south-to-north row order
+    Gtx,
+    // 16 or 32 bit integers, north-to-south row order
+    Byn(byn::BynLayout),
+}
+
+/// A single band grid, read from file on demand.
+#[derive(Debug)]
+pub struct FileGrid {
+    // The georeferencing: A BaseGrid without grid values. Its `offset`
+    // is the position of the first grid value in the file
+    header: BaseGrid,
+    format: Format,
+    file: Mutex<File>,
+    // Recently read rows, in file row order, and their values, oldest first
+    rows: RwLock<VecDeque<(usize, Vec<f32>)>>,
+}
+
+impl FileGrid {
+    /// Open a geoid model in the NOAA/NGS `.gtx` format
+    pub fn gtx(path: &Path) -> Result<Self, Error> {
+        let (file, buf) = open(path, gtx::HEADER_SIZE)?;
+        let (header, rows, cols) = gtx::gtx_header(&buf)?;
+        let mut grid = Self::new(file, &header, (rows, cols), gtx::HEADER_SIZE, Format::Gtx)?;
+        grid.header = grid.header.with_nodata(Some(gtx::NODATA));
+        Ok(grid)
+    }
+
+    /// Open a geoid model in the Natural Resources Canada `.byn` format
+    pub fn byn(path: &Path) -> Result<Self, Error> {
+        let (file, buf) = open(path, byn::HEADER_SIZE)?;
+        let (header, rows, cols, layout) = byn::byn_header(&buf)?;
+        Self::new(
+            file,
+            &header,
+            (rows, cols),
+            byn::HEADER_SIZE,
+            Format::Byn(layout),
+        )
+    }
+
+    // The grid dimensions, `shape`, come straight from the file header, so we
+    // check them against the file length, before trusting them with anything.
+    // Having passed, the positions of all rows are within the file
+    fn new(
+        file: File,
+        header: &[f64],
+        shape: (usize, usize),
+        start: usize,
+        format: Format,
+    ) -> Result<Self, Error> {
+        let length = file.metadata()?.len();
+        let fits = |rows, cols| {
+            file_size(start, value_size(format), rows, cols)
+                .is_some_and(|size| size as u64 <= length)
+        };
+        if !fits(shape.0, shape.1) {
+            return Err(Error::Invalid("Incomplete grid file".to_string()));
+        }
+
+        let header = BaseGrid::plain(header, None, Some(start))?;
+        if !fits(header.rows, header.cols) {
+            return Err(Error::Invalid("Incomplete grid file".to_string()));
+        }
+        Ok(FileGrid {
+            header,
+            format,
+            file: Mutex::new(file),
+            rows: RwLock::new(VecDeque::with_capacity(ROW_CACHE_SIZE)),
+        })
+    }
+
+    // Read the value with index `index` in the north-to-south scan order of
+    // the header grid
+    fn value(&self, index: usize) -> Option<f32> {
+        let (rows, cols) = (self.header.rows, self.header.cols);
+        let node = index - self.header.offset;
+        let (mut row, col) = (node / cols, node % cols);
+        if let Format::Gtx = self.format {
+            row = rows - 1 - row;
+        }
+
+        // Most often, the row is already in the cache
+        let cached = self.rows.read().ok()?;
+        if let Some((_, values)) = cached.iter().find(|(r, _)| *r == row) {
+            return values.get(col).copied();
+        }
+        drop(cached);
+
+        // Otherwise, read it, and make room for it by evicting the oldest row
+        let values = self.read_row(row)?;
+        let value = values.get(col).copied();
+        let mut cached = self.rows.write().ok()?;
+        if !cached.iter().any(|(r, _)| *r == row) {
+            if cached.len() >= ROW_CACHE_SIZE {
+                cached.pop_front();
+            }
+            cached.push_back((row, values));
+        }
+        value
+    }
+
+    // Read and decode the values of row number `row`, in file order
+    fn read_row(&self, row: usize) -> Option<Vec<f32>> {
+        let cols = self.header.cols;
+        let size = value_size(self.format);
+        let position = self.header.offset + size * row * cols;
+        let mut buf = vec![0_u8; size * cols];
+
+        let mut file = self.file.lock().ok()?;
+        let read = file
+            .seek(SeekFrom::Start(position as u64))
+            .and_then(|_| file.read_exact(&mut buf));
+        if let Err(e) = read {
+            warn!("FileGrid: Read failed: {e}");
+            return None;
+        }
+        drop(file);
+
+        let values = buf.chunks_exact(size).map(|bytes| match self.format {
+            Format::Gtx => gtx::gtx_value(bytes),
+            Format::Byn(layout) => layout.value(bytes),
+        });
+        Some(values.collect())
+    }
+}
+
+// The size of each grid value in the file, in bytes
+fn value_size(format: Format) -> usize {
+    match format {
+        Format::Gtx => 4,
+        Format::Byn(layout) => layout.size,
+    }
+}
+
+// Open `path`, and read the first `header_size` bytes
+fn open(path: &Path, header_size: usize) -> Result<(File, Vec<u8>), Error> {
+    let mut file = File::open(path)?;
+    let mut buf = vec![0_u8; header_size];
+    file.read_exact(&mut buf)?;
+    Ok((file, buf))
+}
+
+impl Grid for FileGrid {
+    fn bands(&self) -> usize {
+        1
+    }
+
+    fn contains(&self, position: &Coor4D, margin: f64) -> bool {
+        self.header.contains(position, margin)
+    }
+
+    fn at(&self, at: &Coor4D, margin: f64) -> Option<Coor4D> {
+        self.at_with(at, margin, Interpolation::Bilinear)
+    }
+
+    fn at_with(&self, at: &Coor4D, margin: f64, method: Interpolation) -> Option<Coor4D> {
+        self.header
+            .interpolate(at, margin, method, |index| self.value(index))
+    }
+
+    fn subgrids(&self) -> Vec<GridInfo> {
+        self.header.subgrids()
+    }
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_grid() -> Result<(), Error> {
+        let gtx = FileGrid::gtx(Path::new("geodesy/gtx/test.gtx"))?;
+        let byn = FileGrid::byn(Path::new("geodesy/byn/test.byn"))?;
+        let gravsoft = BaseGrid::gravsoft(&std::fs::read("geodesy/geoid/test.geoid")?)?;
+
+        for c in [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(57.5, 15.25, 0., 0.),
+            Coor4D::geo(58., 16., 0., 0.),
+        ] {
+            let expected = gravsoft.at(&c, 0.).unwrap()[0];
+            assert!((gtx.at(&c, 0.).unwrap()[0] - expected).abs() < 1e-5);
+            assert!((byn.at(&c, 0.).unwrap()[0] - expected).abs() < 1e-5);
+        }
+
+        // Nodata at the 54N 8E node, and outside of the grid
+        let c = Coor4D::geo(54.5, 8.5, 0., 0.);
+        assert!(gtx.at(&c, 0.).is_none());
+        assert!(byn.at(&c, 0.).is_none());
+        let c = Coor4D::geo(50., 8.5, 0., 0.);
+        assert!(gtx.at(&c, 0.5).is_none());
+
+        // Not a BYN file
+        assert!(FileGrid::byn(Path::new("geodesy/gtx/test.gtx")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt_file() -> Result<(), Error> {
+        let buf = std::fs::read("geodesy/gtx/test.gtx")?;
+        let path = std::env::temp_dir().join(format!("corrupt-{}.gtx", std::process::id()));
+
+        // A header claiming a huge grid
+        let mut corrupt = buf.clone();
+        corrupt[32..40].copy_from_slice(&[0x7f, 0xff, 0xff, 0xff, 0x7f, 0xff, 0xff, 0xff]);
+        std::fs::write(&path, &corrupt)?;
+        let huge = FileGrid::gtx(&path);
+
+        // A truncated file
+        std::fs::write(&path, &buf[..buf.len() - 4])?;
+        let truncated = FileGrid::gtx(&path);
+
+        std::fs::remove_file(&path)?;
+        assert!(matches!(huge, Err(Error::Invalid(_))));
+        assert!(matches!(truncated, Err(Error::Invalid(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn row_cache() -> Result<(), Error> {
+        let gtx = std::sync::Arc::new(FileGrid::gtx(Path::new("geodesy/gtx/test.gtx"))?);
+        let gravsoft = BaseGrid::gravsoft(&std::fs::read("geodesy/geoid/test.geoid")?)?;
+
+        // Interpolation between two rows reads just those two rows
+        let c = Coor4D::geo(55.5, 12., 0., 0.);
+        assert!(gtx.at(&c, 0.).is_some());
+        assert_eq!(gtx.rows.read().unwrap().len(), 2);
+
+        // The cache is shared between threads
+        let workers: Vec<_> = (0..8)
+            .map(|i| {
+                let gtx = gtx.clone();
+                let c = Coor4D::geo(54.25 + i as f64 * 0.45, 9.5 + i as f64 * 0.75, 0., 0.);
+                std::thread::spawn(move || (c, gtx.at(&c, 0.)))
+            })
+            .collect();
+        for worker in workers {
+            let (c, value) = worker.join().unwrap();
+            let expected = gravsoft.at(&c, 0.).unwrap()[0];
+            assert!((value.unwrap()[0] - expected).abs() < 1e-5);
+        }
+        assert!(gtx.rows.read().unwrap().len() <= gtx.header.rows);
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+//! A minimal DEFLATE ([RFC 1951](https://www.rfc-editor.org/rfc/rfc1951))
+//! decoder, with support for the zlib wrapper
+//! ([RFC 1950](https://www.rfc-editor.org/rfc/rfc1950)) used by TIFF.
+//! Loosely modelled on Mark Adler's `puff.c`: small and simple
+//! rather than fast, which is fine for decoding grid files once.
+use crate::Error;
+
+const MAX_BITS: usize = 15;
+
+// Base lengths and extra bits for the length codes 257..285
+#[rustfmt::skip]
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+#[rustfmt::skip]
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+// Base offsets and extra bits for the distance codes 0..29
+#[rustfmt::skip]
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+#[rustfmt::skip]
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+// The order of the code length code lengths in a dynamic block header
+const CLEN_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn corrupt() -> Error {
+    Error::Invalid("Corrupt DEFLATE stream".to_string())
+}
+
+/// Decompress a zlib wrapped DEFLATE stream
+pub(crate) fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    if data.len() < 6 {
+        return Err(corrupt());
+    }
+    let (cmf, flg) = (data[0] as u16, data[1] as u16);
+    if cmf & 0x0f != 8 || (cmf * 256 + flg) % 31 != 0 || flg & 0x20 != 0 {
+        return Err(Error::Unsupported("Not a zlib stream".to_string()));
+    }
+    let result = inflate(&data[2..])?;
+
+    // The Adler-32 checksum follows the DEFLATE stream - but we may
+    // not know exactly where it ends, so we check the last 4 bytes
+    let expected = u32::from_be_bytes(data[data.len() - 4..].try_into().unwrap());
+    if adler32(&result) != expected {
+        return Err(Error::Invalid("DEFLATE checksum mismatch".to_string()));
+    }
+    Ok(result)
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+/// Decompress a raw DEFLATE stream
+pub(crate) fn inflate(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut state = State {
+        data,
+        pos: 0,
+        bitbuf: 0,
+        bitcnt: 0,
+        out: Vec::with_capacity(data.len() * 4),
+    };
+
+    loop {
+        let last = state.bits(1)?;
+        match state.bits(2)? {
+            0 => state.stored()?,
+            1 => state.fixed()?,
+            2 => state.dynamic()?,
+            _ => return Err(corrupt()),
+        }
+        if last == 1 {
+            break;
+        }
+    }
+    Ok(state.out)
+}
+
+// Canonical Huffman decoding table: The number of codes of each length,
+// and the symbols ordered by code
+struct Huffman {
+    count: [u16; MAX_BITS + 1],
+    symbol: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Huffman, Error> {
+        let mut count = [0_u16; MAX_BITS + 1];
+        for &len in lengths {
+            count[len as usize] += 1;
+        }
+
+        // Check for an over-subscribed code
+        let mut left = 1_i32;
+        for &n in &count[1..] {
+            left = (left << 1) - n as i32;
+            if left < 0 {
+                return Err(corrupt());
+            }
+        }
+
+        let mut offs = [0_u16; MAX_BITS + 1];
+        for len in 1..MAX_BITS {
+            offs[len + 1] = offs[len] + count[len];
+        }
+
+        let mut symbol = vec![0_u16; lengths.len()];
+        for (sym, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbol[offs[len as usize] as usize] = sym as u16;
+                offs[len as usize] += 1;
+            }
+        }
+        Ok(Huffman { count, symbol })
+    }
+}
+
+struct State<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bitbuf: u32,
+    bitcnt: u32,
+    out: Vec<u8>,
+}
+
+impl State<'_> {
+    // Read `need` bits, LSB first
+    fn bits(&mut self, need: u32) -> Result<u32, Error> {
+        let mut val = self.bitbuf;
+        while self.bitcnt < need {
+            let Some(&byte) = self.data.get(self.pos) else {
+                return Err(corrupt());
+            };
+            self.pos += 1;
+            val |= (byte as u32) << self.bitcnt;
+            self.bitcnt += 8;
+        }
+        self.bitbuf = if need == 32 { 0 } else { val >> need };
+        self.bitcnt -= need;
+        Ok(val & ((1_u64 << need) - 1) as u32)
+    }
+
+    fn decode(&mut self, h: &Huffman) -> Result<u16, Error> {
+        let (mut code, mut first, mut index) = (0_i32, 0_i32, 0_i32);
+        for len in 1..=MAX_BITS {
+            code |= self.bits(1)? as i32;
+            let count = h.count[len] as i32;
+            if code - count < first {
+                return Ok(h.symbol[(index + (code - first)) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(corrupt())
+    }
+
+    fn stored(&mut self) -> Result<(), Error> {
+        // Discard the remaining bits of the current byte
+        self.bitbuf = 0;
+        self.bitcnt = 0;
+
+        let Some(header) = self.data.get(self.pos..self.pos + 4) else {
+            return Err(corrupt());
+        };
+        let len = u16::from_le_bytes([header[0], header[1]]);
+        let nlen = u16::from_le_bytes([header[2], header[3]]);
+        if len != !nlen {
+            return Err(corrupt());
+        }
+        self.pos += 4;
+
+        let Some(block) = self.data.get(self.pos..self.pos + len as usize) else {
+            return Err(corrupt());
+        };
+        self.out.extend_from_slice(block);
+        self.pos += len as usize;
+        Ok(())
+    }
+
+    fn fixed(&mut self) -> Result<(), Error> {
+        let mut lengths = [0_u8; 288];
+        lengths[0..144].fill(8);
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        lengths[280..288].fill(8);
+        let lencode = Huffman::new(&lengths)?;
+        let distcode = Huffman::new(&[5_u8; 30])?;
+        self.codes(&lencode, &distcode)
+    }
+
+    fn dynamic(&mut self) -> Result<(), Error> {
+        let nlen = self.bits(5)? as usize + 257;
+        let ndist = self.bits(5)? as usize + 1;
+        let ncode = self.bits(4)? as usize + 4;
+        if nlen > 286 || ndist > 30 {
+            return Err(corrupt());
+        }
+
+        // The code lengths for the code length alphabet
+        let mut lengths = [0_u8; 320];
+        for &index in CLEN_ORDER.iter().take(ncode) {
+            lengths[index] = self.bits(3)? as u8;
+        }
+        let lencode = Huffman::new(&lengths[0..19])?;
+
+        // The literal/length and distance code lengths
+        let mut index = 0;
+        while index < nlen + ndist {
+            let symbol = self.decode(&lencode)?;
+            if symbol < 16 {
+                lengths[index] = symbol as u8;
+                index += 1;
+                continue;
+            }
+
+            let (len, repeat) = match symbol {
+                16 => {
+                    if index == 0 {
+                        return Err(corrupt());
+                    }
+                    (lengths[index - 1], 3 + self.bits(2)?)
+                }
+                17 => (0, 3 + self.bits(3)?),
+                _ => (0, 11 + self.bits(7)?),
+            };
+            if index + repeat as usize > nlen + ndist {
+                return Err(corrupt());
+            }
+            for _ in 0..repeat {
+                lengths[index] = len;
+                index += 1;
+            }
+        }
+
+        // An end-of-block code is mandatory
+        if lengths[256] == 0 {
+            return Err(corrupt());
+        }
+
+        let lencode = Huffman::new(&lengths[0..nlen])?;
+        let distcode = Huffman::new(&lengths[nlen..nlen + ndist])?;
+        self.codes(&lencode, &distcode)
+    }
+
+    // Decode literals and length/distance pairs until end-of-block
+    fn codes(&mut self, lencode: &Huffman, distcode: &Huffman) -> Result<(), Error> {
+        loop {
+            let symbol = self.decode(lencode)? as usize;
+            if symbol < 256 {
+                self.out.push(symbol as u8);
+                continue;
+            }
+            if symbol == 256 {
+                return Ok(());
+            }
+
+            let symbol = symbol - 257;
+            if symbol >= 29 {
+                return Err(corrupt());
+            }
+            let len =
+                LENGTH_BASE[symbol] as usize + self.bits(LENGTH_EXTRA[symbol] as u32)? as usize;
+
+            let symbol = self.decode(distcode)? as usize;
+            if symbol >= 30 {
+                return Err(corrupt());
+            }
+            let dist = DIST_BASE[symbol] as usize + self.bits(DIST_EXTRA[symbol] as u32)? as usize;
+            if dist > self.out.len() {
+                return Err(corrupt());
+            }
+
+            // The source and destination may overlap, so we copy byte by byte
+            let start = self.out.len() - dist;
+            for i in 0..len {
+                let byte = self.out[start + i];
+                self.out.push(byte);
+            }
+        }
+    }
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inflate() -> Result<(), Error> {
+        // "Hello, hello, hello!" compressed with zlib, using fixed Huffman codes
+        #[rustfmt::skip]
+        let compressed = [
+            0x78, 0xda, 0xf3, 0x48, 0xcd, 0xc9, 0xc9, 0xd7, 0x51, 0xc8, 0x40, 0xa2, 0x14,
+            0x01, 0x48, 0x9e, 0x06, 0xd6,
+        ];
+        let result = zlib_decompress(&compressed)?;
+        assert_eq!(result, b"Hello, hello, hello!");
+
+        // A skewed distribution of letters, compressed using dynamic Huffman codes
+        #[rustfmt::skip]
+        let compressed = [
+            0x78, 0xda, 0x1d, 0x88, 0xc1, 0x11, 0x00, 0x30, 0x0c, 0x40, 0x66, 0x25, 0xf6,
+            0x9f, 0xa1, 0x69, 0x1e, 0xee, 0x20, 0x03, 0xf2, 0x59, 0x09, 0x26, 0xdb, 0xd6,
+            0xfb, 0xa9, 0xe1, 0xf4, 0x00, 0x39, 0x8c, 0x0f, 0x51,
+        ];
+        let result = zlib_decompress(&compressed)?;
+        assert_eq!(result, b"bacaabaaabacaadaacdbdbaabbcaabadbbbdabcd");
+
+        // Stored (uncompressed) block
+        let stored = [0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'];
+        assert_eq!(super::inflate(&stored)?, b"abc");
+
+        // Garbage in, error out
+        assert!(zlib_decompress(&[0x78, 0x9c, 0xff, 0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(zlib_decompress(b"Not compressed").is_err());
+        Ok(())
+    }
+}
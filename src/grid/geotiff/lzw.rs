@@ -0,0 +1,113 @@
+//! The TIFF flavour of LZW decompression (TIFF 6.0 specification, section 13):
+//! MSB-first codes of 9 to 12 bits, with the code width incremented one code
+//! early (the "early change" convention).
+use crate::Error;
+
+const CLEAR: usize = 256;
+const EOI: usize = 257;
+const MAX_WIDTH: u32 = 12;
+
+pub(crate) fn lzw_decompress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::with_capacity(data.len() * 3);
+
+    // The string table is represented by (prefix code, last byte, length)
+    // triplets, so each entry can be unwound into the output buffer
+    let mut table: Vec<(usize, u8, usize)> = (0..256).map(|i| (usize::MAX, i as u8, 1)).collect();
+    table.push((usize::MAX, 0, 0)); // CLEAR
+    table.push((usize::MAX, 0, 0)); // EOI
+
+    let mut width = 9_u32;
+    let mut old: Option<usize> = None;
+    let (mut bitbuf, mut bitcnt, mut pos) = (0_u32, 0_u32, 0_usize);
+
+    loop {
+        // Read the next code, MSB first
+        while bitcnt < width {
+            let Some(&byte) = data.get(pos) else {
+                // Missing EOI: Accept what we have
+                return Ok(out);
+            };
+            pos += 1;
+            bitbuf = (bitbuf << 8) | byte as u32;
+            bitcnt += 8;
+        }
+        bitcnt -= width;
+        let code = ((bitbuf >> bitcnt) & ((1 << width) - 1)) as usize;
+
+        if code == EOI {
+            return Ok(out);
+        }
+
+        if code == CLEAR {
+            table.truncate(258);
+            width = 9;
+            old = None;
+            continue;
+        }
+
+        let Some(prev) = old else {
+            // The first code after a CLEAR must be a literal
+            if code > 255 {
+                return Err(Error::Invalid("Corrupt LZW stream".to_string()));
+            }
+            out.push(code as u8);
+            old = Some(code);
+            continue;
+        };
+
+        // Emit the string for the code, and extend the table with the previous
+        // string plus the first byte of the current one. If the code is not yet
+        // in the table, it must be the one we are about to add
+        let start = out.len();
+        let first = if code < table.len() {
+            unwind(&table, code, &mut out);
+            out[start]
+        } else if code == table.len() {
+            unwind(&table, prev, &mut out);
+            let first = out[start];
+            out.push(first);
+            first
+        } else {
+            return Err(Error::Invalid("Corrupt LZW stream".to_string()));
+        };
+
+        if table.len() < 1 << MAX_WIDTH {
+            table.push((prev, first, table[prev].2 + 1));
+        }
+        if table.len() + 1 >= 1 << width && width < MAX_WIDTH {
+            width += 1;
+        }
+        old = Some(code);
+    }
+}
+
+// Append the string represented by `code` to `out`
+fn unwind(table: &[(usize, u8, usize)], code: usize, out: &mut Vec<u8>) {
+    let len = table[code].2;
+    let start = out.len();
+    out.resize(start + len, 0);
+    let mut code = code;
+    for i in (0..len).rev() {
+        let (prefix, byte, _) = table[code];
+        out[start + i] = byte;
+        code = prefix;
+    }
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lzw() -> Result<(), Error> {
+        // The codes 256, 7, 258, 8, 8, 258, 6, 257 for the input
+        // 07 07 07 08 08 07 07 06, packed into 9 bit codes. Note that
+        // the first use of code 258 precedes its entry in the table
+        let compressed = [0x80, 0x01, 0xe0, 0x40, 0x80, 0x44, 0x08, 0x0d, 0x01];
+        let result = lzw_decompress(&compressed)?;
+        assert_eq!(result, [7, 7, 7, 8, 8, 7, 7, 6]);
+        Ok(())
+    }
+}
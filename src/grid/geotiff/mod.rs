@@ -0,0 +1,462 @@
+mod inflate;
+mod lzw;
+mod tiff;
+
+use super::BaseGrid;
+use crate::{
+    coord::Coor4D,
+    grid::{Grid, GridInfo, Interpolation},
+    Error,
+};
+use tiff::*;
+
+/// Grid in the [PROJ GeoTIFF grid profile](https://github.com/OSGeo/PROJ-data/blob/master/grid_tools/GTiff_grid_profile.md).
+///
+/// Supports horizontal offset grids (`latitude_offset`, `longitude_offset`),
+/// geoid models (`geoid_undulation`) and deformation models
+/// (`east_velocity`, `north_velocity`, `up_velocity`), as one or more
+/// subgrids, each stored as a separate TIFF image. Band values are
+/// normalized to the conventions of the Gravsoft grids: Radians for
+/// horizontal offsets (in longitude/latitude order), meters for geoid
+/// undulations, and meters/year for velocities (in east/north/up order).
+#[derive(Debug, Default, Clone)]
+pub struct GeoTiffGrid {
+    subgrids: Vec<BaseGrid>,
+}
+
+impl GeoTiffGrid {
+    pub fn new(buf: &[u8]) -> Result<Self, Error> {
+        let tiff = Tiff::new(buf)?;
+        let mut subgrids = Vec::new();
+        for ifd in tiff.ifds()? {
+            // Skip reduced resolution versions (overviews) and transparency masks
+            let subfile_type = tiff.number(&ifd, NEW_SUBFILE_TYPE, Some(0.))? as u32;
+            if subfile_type & 5 != 0 {
+                continue;
+            }
+            subgrids.push(subgrid(&tiff, &ifd)?);
+        }
+
+        if subgrids.is_empty() {
+            return Err(Error::Invalid("GeoTIFF: No grids in file".to_string()));
+        }
+        Ok(Self { subgrids })
+    }
+
+    // The subgrids may be nested, and the PROJ GeoTIFF profile does not guarantee
+    // any particular order, so we select the finest subgrid containing the point.
+    // If none does, we fall back to the first subgrid containing the point within
+    // the margin
+    fn find_grid(&self, coord: &Coor4D, margin: f64) -> Option<&BaseGrid> {
+        let cell_area = |grid: &&BaseGrid| (grid.dlat * grid.dlon).abs();
+        let finest = self
+            .subgrids
+            .iter()
+            .filter(|grid| grid.contains(coord, 0.))
+            .reduce(|best, grid| {
+                if cell_area(&grid) < cell_area(&best) {
+                    grid
+                } else {
+                    best
+                }
+            });
+        if finest.is_some() {
+            return finest;
+        }
+        self.subgrids
+            .iter()
+            .find(|grid| grid.contains(coord, margin))
+    }
+}
+
+impl Grid for GeoTiffGrid {
+    fn bands(&self) -> usize {
+        self.subgrids[0].bands
+    }
+
+    /// Checks if a `Coord4D` is within the grid limits +- `margin` grid units
+    fn contains(&self, position: &Coor4D, margin: f64) -> bool {
+        self.find_grid(position, margin).is_some()
+    }
+
+    fn at(&self, coord: &Coor4D, margin: f64) -> Option<Coor4D> {
+        self.find_grid(coord, margin)
+            .and_then(|grid| grid.at(coord, margin))
+    }
+
+    fn at_with(&self, coord: &Coor4D, margin: f64, method: Interpolation) -> Option<Coor4D> {
+        self.find_grid(coord, margin)
+            .and_then(|grid| grid.at_with(coord, margin, method))
+    }
+
+    /// The subgrids (i.e. TIFF images) in file order, named by their index
+    fn subgrids(&self) -> Vec<GridInfo> {
+        let subgrids = self.subgrids.iter().enumerate();
+        subgrids
+            .map(|(i, grid)| grid.info(&i.to_string()))
+            .collect()
+    }
+}
+
+// Per-sample metadata, as given by the GDAL_METADATA tag
+#[derive(Debug, Default, Clone)]
+struct SampleInfo {
+    description: String,
+    unit: String,
+    scale: f64,
+    offset: f64,
+    positive_west: bool,
+}
+
+// Read the georeferencing and band values of a single TIFF image
+fn subgrid(tiff: &Tiff, ifd: &Ifd) -> Result<BaseGrid, Error> {
+    let invalid = |message: &str| Error::Invalid(format!("GeoTIFF: {message}"));
+    let unsupported = |message: &str| Error::Unsupported(format!("GeoTIFF: {message}"));
+
+    // GeoKeys: We need a geographic model, in degrees, and the raster type
+    let keys = tiff
+        .numbers(ifd, GEO_KEY_DIRECTORY)?
+        .ok_or_else(|| invalid("Missing GeoKeyDirectory"))?;
+    let mut pixel_is_area = true;
+    for key in keys.chunks_exact(4).skip(1) {
+        // Only keys with inline values (location 0) are of interest here
+        if key[1] != 0. {
+            continue;
+        }
+        match (key[0] as u32, key[3]) {
+            // GTModelTypeGeoKey: 2 is ModelTypeGeographic
+            (1024, model) if model != 2. => return Err(unsupported("Grid is not geographic")),
+            // GTRasterTypeGeoKey: 1 is RasterPixelIsArea, 2 is RasterPixelIsPoint
+            (1025, raster) => pixel_is_area = raster != 2.,
+            // GeogAngularUnitsGeoKey: 9102 is degree
+            (2054, unit) if unit != 9102. => return Err(unsupported("Grid is not in degrees")),
+            _ => (),
+        }
+    }
+
+    let scale = tiff
+        .numbers(ifd, MODEL_PIXEL_SCALE)?
+        .ok_or_else(|| invalid("Missing ModelPixelScale"))?;
+    let tiepoint = tiff
+        .numbers(ifd, MODEL_TIEPOINT)?
+        .ok_or_else(|| invalid("Missing ModelTiepoint"))?;
+    if scale.len() < 2 || tiepoint.len() < 6 {
+        return Err(invalid("Malformed georeferencing"));
+    }
+
+    let (rows, cols, mut bands) = tiff.bands(ifd)?;
+    if rows < 2 || cols < 2 {
+        return Err(invalid("Grid must have at least 2 rows and 2 columns"));
+    }
+
+    // The location of the north western grid node. For pixel-is-area, the
+    // tiepoint refers to the corner of the cell, rather than its center
+    let (dlon, dlat) = (scale[0], scale[1]);
+    let mut lon_w = tiepoint[3] - tiepoint[0] * dlon;
+    let mut lat_n = tiepoint[4] + tiepoint[1] * dlat;
+    if pixel_is_area {
+        lon_w += dlon / 2.;
+        lat_n -= dlat / 2.;
+    }
+    let lon_e = lon_w + (cols - 1) as f64 * dlon;
+    let lat_s = lat_n - (rows - 1) as f64 * dlat;
+
+    // Nodes without data become NaN, before scale and offset are applied
+    let nodata = tiff.text(ifd, GDAL_NODATA);
+    if let Some(nodata) = nodata.and_then(|n| n.trim().parse::<f32>().ok()) {
+        for value in bands.iter_mut().flatten() {
+            if *value == nodata {
+                *value = f32::NAN;
+            }
+        }
+    }
+
+    // Apply scale and offset, and identify the bands
+    let info = sample_info(tiff.text(ifd, GDAL_METADATA).as_deref(), bands.len());
+    for (band, info) in bands.iter_mut().zip(&info) {
+        if info.scale != 1. || info.offset != 0. {
+            for value in band.iter_mut() {
+                *value = (*value as f64 * info.scale + info.offset) as f32;
+            }
+        }
+    }
+    let find = |description: &str| info.iter().position(|i| i.description == description);
+
+    let grid: Vec<Vec<f32>>;
+    if let (Some(lat), Some(lon)) = (find("latitude_offset"), find("longitude_offset")) {
+        // Horizontal offsets: Convert to radians, in longitude/latitude order
+        let mut lon_band = std::mem::take(&mut bands[lon]);
+        let mut lat_band = std::mem::take(&mut bands[lat]);
+        to_radians(&mut lon_band, &info[lon].unit)?;
+        to_radians(&mut lat_band, &info[lat].unit)?;
+        if info[lon].positive_west {
+            lon_band.iter_mut().for_each(|v| *v = -*v);
+        }
+        grid = vec![lon_band, lat_band];
+    } else if let Some(geoid) = find("geoid_undulation") {
+        let mut band = std::mem::take(&mut bands[geoid]);
+        to_meters(&mut band, &info[geoid].unit)?;
+        grid = vec![band];
+    } else if let (Some(east), Some(north), Some(up)) = (
+        find("east_velocity"),
+        find("north_velocity"),
+        find("up_velocity"),
+    ) {
+        grid = [east, north, up]
+            .iter()
+            .map(|&b| {
+                let mut band = std::mem::take(&mut bands[b]);
+                to_meters(&mut band, info[b].unit.trim_end_matches(" per year"))?;
+                Ok(band)
+            })
+            .collect::<Result<_, Error>>()?;
+    } else {
+        return Err(unsupported("Unrecognized band descriptions"));
+    }
+
+    // Interleave the bands, node by node, as expected by BaseGrid
+    if grid.iter().any(|band| band.len() != rows * cols) {
+        return Err(invalid("Band size does not match the image size"));
+    }
+    let mut values = Vec::with_capacity(rows * cols * grid.len());
+    for i in 0..rows * cols {
+        values.extend(grid.iter().map(|band| band[i]));
+    }
+
+    #[rustfmt::skip]
+    let header = [
+        lat_n.to_radians(), lat_s.to_radians(), lon_w.to_radians(), lon_e.to_radians(),
+        dlat.to_radians(), dlon.to_radians(), grid.len() as f64,
+    ];
+    BaseGrid::plain(&header, Some(&values), None)
+}
+
+// Extract the per-sample metadata from the GDAL XML metadata. In the absence
+// of band descriptions, the band meaning is inferred from the band count,
+// following the conventions of the Gravsoft grids
+fn sample_info(xml: Option<&str>, bands: usize) -> Vec<SampleInfo> {
+    #[rustfmt::skip]
+    let defaults: &[(&str, &str)] = match bands {
+        1 => &[("geoid_undulation", "metre")],
+        2 => &[("latitude_offset", "arc-second"), ("longitude_offset", "arc-second")],
+        _ => &[
+            ("east_velocity", "millimetres per year"),
+            ("north_velocity", "millimetres per year"),
+            ("up_velocity", "millimetres per year"),
+        ],
+    };
+
+    let mut info = vec![SampleInfo::default(); bands];
+    for (i, sample) in info.iter_mut().enumerate() {
+        let (description, unit) = defaults.get(i).copied().unwrap_or_default();
+        sample.description = description.to_string();
+        sample.unit = unit.to_string();
+        sample.scale = 1.;
+    }
+
+    // The metadata are a flat list of items, like
+    // <Item name="DESCRIPTION" sample="0" role="description">latitude_offset</Item>
+    let Some(xml) = xml else {
+        return info;
+    };
+    for item in xml.split("<Item").skip(1) {
+        let Some((attributes, rest)) = item.split_once('>') else {
+            continue;
+        };
+        let Some((value, _)) = rest.split_once("</Item") else {
+            continue;
+        };
+        let attribute = |name: &str| {
+            let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+            let end = attributes[start..].find('"')? + start;
+            Some(attributes[start..end].to_string())
+        };
+        let Some(sample) = attribute("sample").and_then(|s| s.parse::<usize>().ok()) else {
+            continue;
+        };
+        let Some(sample) = info.get_mut(sample) else {
+            continue;
+        };
+
+        let value = value.trim();
+        match attribute("name").unwrap_or_default().as_str() {
+            "DESCRIPTION" => sample.description = value.to_string(),
+            "UNITTYPE" => sample.unit = value.to_string(),
+            "SCALE" => sample.scale = value.parse().unwrap_or(1.),
+            "OFFSET" => sample.offset = value.parse().unwrap_or(0.),
+            "positive_value" => sample.positive_west = value == "west",
+            _ => (),
+        }
+    }
+    info
+}
+
+fn to_radians(band: &mut [f32], unit: &str) -> Result<(), Error> {
+    let factor = match unit {
+        "arc-second" => 1_f64.to_radians() / 3600.,
+        "degree" => 1_f64.to_radians(),
+        "radian" => 1.,
+        _ => return Err(Error::Unsupported(format!("GeoTIFF: Unknown unit {unit}"))),
+    };
+    band.iter_mut()
+        .for_each(|v| *v = (*v as f64 * factor) as f32);
+    Ok(())
+}
+
+fn to_meters(band: &mut [f32], unit: &str) -> Result<(), Error> {
+    let factor = match unit {
+        "metre" | "meter" | "metres" | "meters" => 1.,
+        "millimetre" | "millimeter" | "millimetres" | "millimeters" => 1e-3,
+        _ => return Err(Error::Unsupported(format!("GeoTIFF: Unknown unit {unit}"))),
+    };
+    if factor != 1. {
+        band.iter_mut()
+            .for_each(|v| *v = (*v as f64 * factor) as f32);
+    }
+    Ok(())
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate::AngularUnits;
+
+    fn read(name: &str) -> Result<GeoTiffGrid, Error> {
+        let buf = std::fs::read(format!("geodesy/tif/{name}"))?;
+        GeoTiffGrid::new(&buf)
+    }
+
+    fn gravsoft(name: &str) -> Result<BaseGrid, Error> {
+        let buf = std::fs::read(name)?;
+        BaseGrid::gravsoft(&buf)
+    }
+
+    #[test]
+    fn datum() -> Result<(), Error> {
+        // Tiled, separate planes, DEFLATE, floating point predictor
+        let tif = read("test_datum.tif")?;
+        let gravsoft = gravsoft("geodesy/datum/test.datum")?;
+        assert_eq!(tif.bands(), 2);
+
+        for c in [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(57.3, 8.7, 0., 0.),
+        ] {
+            let expected = gravsoft.at(&c, 0.).unwrap();
+            let d = tif.at(&c, 0.).unwrap();
+            assert!((d[0] - expected[0]).abs() < 1e-10);
+            assert!((d[1] - expected[1]).abs() < 1e-10);
+        }
+
+        // The grid values equal the coordinates, in arc-seconds
+        let d = tif.at(&Coor4D::geo(55.5, 12.25, 0., 0.), 0.).unwrap();
+        assert!((d.to_arcsec()[0] - 12.25).abs() < 1e-4);
+        assert!((d.to_arcsec()[1] - 55.5).abs() < 1e-4);
+
+        // Outside, and inside the margin
+        assert!(tif.at(&Coor4D::geo(60., 12., 0., 0.), 0.5).is_none());
+        assert!(tif.at(&Coor4D::geo(58.2, 12., 0., 0.), 0.5).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn geoid() -> Result<(), Error> {
+        // Big endian, stripped, LZW compressed, pixel-is-area
+        let tif = read("test_geoid.tif")?;
+        let gravsoft = gravsoft("geodesy/geoid/test.geoid")?;
+        assert_eq!(tif.bands(), 1);
+        for c in [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(54.25, 15.5, 0., 0.),
+        ] {
+            let expected = gravsoft.at(&c, 0.).unwrap();
+            let d = tif.at(&c, 0.).unwrap();
+            assert!((d[0] - expected[0]).abs() < 1e-5);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn velocity() -> Result<(), Error> {
+        // Stripped, chunky planes, DEFLATE, horizontal predictor
+        let tif = read("test_velocity.tif")?;
+        let gravsoft = gravsoft("geodesy/deformation/test.deformation")?;
+        assert_eq!(tif.bands(), 3);
+        for c in [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(56.5, 9.5, 0., 0.),
+        ] {
+            let expected = gravsoft.at(&c, 0.).unwrap();
+            let d = tif.at(&c, 0.).unwrap();
+            for i in 0..3 {
+                assert!((d[i] - expected[i]).abs() < 1e-9);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn subgrids() -> Result<(), Error> {
+        let tif = read("test_subgrid.tif")?;
+
+        // Inside the child grid: 2" shift, with longitude positive west
+        let d = tif
+            .at(&Coor4D::geo(55.5, 13., 0., 0.), 0.)
+            .unwrap()
+            .to_arcsec();
+        assert!((d[0] + 2.).abs() < 1e-6);
+        assert!((d[1] - 2.).abs() < 1e-6);
+
+        // Outside the child grid: 1" shift
+        let d = tif
+            .at(&Coor4D::geo(57., 13., 0., 0.), 0.)
+            .unwrap()
+            .to_arcsec();
+        assert!((d[0] + 1.).abs() < 1e-6);
+        assert!((d[1] - 1.).abs() < 1e-6);
+
+        // Not a TIFF
+        assert!(GeoTiffGrid::new(b"NUM_OREC").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt() -> Result<(), Error> {
+        // A 100000 x 100000 image, claimed to be stored in a single 4 byte strip
+        #[rustfmt::skip]
+        let entries = [
+            (IMAGE_WIDTH,       4_u16, 1_u32, 100_000_u32),
+            (IMAGE_LENGTH,      4,     1,     100_000),
+            (BITS_PER_SAMPLE,   3,     1,     32),
+            (STRIP_OFFSETS,     4,     1,     0),
+            (STRIP_BYTE_COUNTS, 4,     1,     4),
+            (SAMPLE_FORMAT,     3,     1,     3),
+        ];
+        let mut buf = b"II*\0\x08\0\0\0".to_vec();
+        buf.extend((entries.len() as u16).to_le_bytes());
+        for (tag, field_type, count, value) in entries {
+            buf.extend(tag.to_le_bytes());
+            buf.extend(field_type.to_le_bytes());
+            buf.extend(count.to_le_bytes());
+            buf.extend(value.to_le_bytes());
+        }
+        buf.extend(0_u32.to_le_bytes());
+
+        // Must be rejected, rather than allocated
+        let tiff = Tiff::new(&buf)?;
+        let ifds = tiff.ifds()?;
+        assert!(matches!(tiff.bands(&ifds[0]), Err(Error::Invalid(_))));
+
+        // A tag claiming more values than the file holds
+        let strip_offsets_count = 8 + 2 + 3 * 12 + 4;
+        buf[strip_offsets_count..strip_offsets_count + 4]
+            .copy_from_slice(&1_000_000_000_u32.to_le_bytes());
+        let tiff = Tiff::new(&buf)?;
+        let ifds = tiff.ifds()?;
+        let offsets = tiff.numbers(&ifds[0], STRIP_OFFSETS);
+        assert!(matches!(offsets, Err(Error::Invalid(_))));
+        Ok(())
+    }
+}
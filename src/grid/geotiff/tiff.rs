@@ -0,0 +1,365 @@
+//! Just enough of the TIFF 6.0 specification to read the image data of
+//! the PROJ GeoTIFF grid profile: Classic (not Big-) TIFF, in either byte
+//! order, with 32 bit floating point samples, tiled or stripped storage,
+//! chunky or separate planar configuration, no, DEFLATE or LZW compression,
+//! and the horizontal or floating point predictors.
+use super::inflate::zlib_decompress;
+use super::lzw::lzw_decompress;
+use crate::Error;
+use std::collections::BTreeMap;
+
+// Baseline and extension TIFF tags
+pub(super) const NEW_SUBFILE_TYPE: u16 = 254;
+pub(super) const IMAGE_WIDTH: u16 = 256;
+pub(super) const IMAGE_LENGTH: u16 = 257;
+pub(super) const BITS_PER_SAMPLE: u16 = 258;
+pub(super) const COMPRESSION: u16 = 259;
+pub(super) const STRIP_OFFSETS: u16 = 273;
+pub(super) const SAMPLES_PER_PIXEL: u16 = 277;
+pub(super) const ROWS_PER_STRIP: u16 = 278;
+pub(super) const STRIP_BYTE_COUNTS: u16 = 279;
+pub(super) const PLANAR_CONFIGURATION: u16 = 284;
+pub(super) const PREDICTOR: u16 = 317;
+pub(super) const TILE_WIDTH: u16 = 322;
+pub(super) const TILE_LENGTH: u16 = 323;
+pub(super) const TILE_OFFSETS: u16 = 324;
+pub(super) const TILE_BYTE_COUNTS: u16 = 325;
+pub(super) const SAMPLE_FORMAT: u16 = 339;
+
+// GeoTIFF tags
+pub(super) const MODEL_PIXEL_SCALE: u16 = 33550;
+pub(super) const MODEL_TIEPOINT: u16 = 33922;
+pub(super) const GEO_KEY_DIRECTORY: u16 = 34735;
+
+// GDAL tags
+pub(super) const GDAL_METADATA: u16 = 42112;
+pub(super) const GDAL_NODATA: u16 = 42113;
+
+// Upper bound on the expansion of compressed image data: DEFLATE expands at
+// most 1032 times, and 12 bit LZW at most about 2600 times
+const MAX_EXPANSION: usize = 4096;
+
+fn invalid(message: &str) -> Error {
+    Error::Invalid(format!("TIFF: {message}"))
+}
+
+fn unsupported(message: &str) -> Error {
+    Error::Unsupported(format!("TIFF: {message}"))
+}
+
+/// A TIFF file, borrowed from a byte buffer
+pub(super) struct Tiff<'a> {
+    buf: &'a [u8],
+    big_endian: bool,
+}
+
+/// An Image File Directory: The tags of one image (i.e. one subgrid)
+#[derive(Debug, Default)]
+pub(super) struct Ifd {
+    // Tag -> (field type, count, offset of the value(s) in the buffer)
+    entries: BTreeMap<u16, (u16, usize, usize)>,
+}
+
+impl<'a> Tiff<'a> {
+    pub(super) fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        let big_endian = match buf.get(0..2) {
+            Some(b"II") => false,
+            Some(b"MM") => true,
+            _ => return Err(unsupported("Not a TIFF file")),
+        };
+        let tiff = Tiff { buf, big_endian };
+        match tiff.u16(2)? {
+            42 => Ok(tiff),
+            43 => Err(unsupported("BigTIFF is not supported")),
+            _ => Err(unsupported("Not a TIFF file")),
+        }
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+        let Some(bytes) = self.buf.get(offset..offset + N) else {
+            return Err(invalid("Unexpected end of file"));
+        };
+        let mut result: [u8; N] = bytes.try_into().unwrap();
+        if self.big_endian {
+            result.reverse();
+        }
+        Ok(result)
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, Error> {
+        Ok(u16::from_le_bytes(self.bytes(offset)?))
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.bytes(offset)?))
+    }
+
+    /// All image file directories, in file order
+    pub(super) fn ifds(&self) -> Result<Vec<Ifd>, Error> {
+        let mut ifds = Vec::new();
+        let mut offset = self.u32(4)? as usize;
+        while offset != 0 {
+            // Guard against circular IFD chains
+            if ifds.len() > 10_000 {
+                return Err(invalid("Too many IFDs"));
+            }
+
+            let count = self.u16(offset)? as usize;
+            let mut ifd = Ifd::default();
+            for i in 0..count {
+                let entry = offset + 2 + 12 * i;
+                let tag = self.u16(entry)?;
+                let field_type = self.u16(entry + 2)?;
+                let n = self.u32(entry + 4)? as usize;
+                let size = n.saturating_mul(field_size(field_type));
+                let value_offset = if size <= 4 {
+                    entry + 8
+                } else {
+                    self.u32(entry + 8)? as usize
+                };
+                ifd.entries.insert(tag, (field_type, n, value_offset));
+            }
+            ifds.push(ifd);
+            offset = self.u32(offset + 2 + 12 * count)? as usize;
+        }
+        Ok(ifds)
+    }
+
+    /// The numeric value(s) of `tag`, converted to f64
+    pub(super) fn numbers(&self, ifd: &Ifd, tag: u16) -> Result<Option<Vec<f64>>, Error> {
+        let Some(&(field_type, count, offset)) = ifd.entries.get(&tag) else {
+            return Ok(None);
+        };
+        let size = field_size(field_type);
+
+        // Check that the values are inside the buffer, before allocating room for them
+        let end = count.checked_mul(size).and_then(|n| n.checked_add(offset));
+        if !end.is_some_and(|end| end <= self.buf.len()) {
+            return Err(invalid("Bad tag"));
+        }
+        let mut result = Vec::with_capacity(count);
+        for i in 0..count {
+            let at = offset + i * size;
+            let value = match field_type {
+                1 | 7 => *self.buf.get(at).ok_or_else(|| invalid("Bad tag"))? as f64,
+                6 => *self.buf.get(at).ok_or_else(|| invalid("Bad tag"))? as i8 as f64,
+                3 => self.u16(at)? as f64,
+                8 => self.u16(at)? as i16 as f64,
+                4 => self.u32(at)? as f64,
+                9 => self.u32(at)? as i32 as f64,
+                5 => self.u32(at)? as f64 / self.u32(at + 4)? as f64,
+                10 => self.u32(at)? as i32 as f64 / self.u32(at + 4)? as i32 as f64,
+                11 => f32::from_le_bytes(self.bytes(at)?) as f64,
+                12 => f64::from_le_bytes(self.bytes(at)?),
+                _ => return Err(unsupported("Unknown field type")),
+            };
+            result.push(value);
+        }
+        Ok(Some(result))
+    }
+
+    /// The first numeric value of `tag`, or `default` if the tag is missing
+    pub(super) fn number(&self, ifd: &Ifd, tag: u16, default: Option<f64>) -> Result<f64, Error> {
+        match self.numbers(ifd, tag)? {
+            Some(values) if !values.is_empty() => Ok(values[0]),
+            _ => default.ok_or_else(|| invalid(&format!("Missing tag {tag}"))),
+        }
+    }
+
+    /// The ASCII value of `tag`, without the trailing NUL
+    pub(super) fn text(&self, ifd: &Ifd, tag: u16) -> Option<String> {
+        let &(field_type, count, offset) = ifd.entries.get(&tag)?;
+        if field_type != 2 {
+            return None;
+        }
+        let bytes = self.buf.get(offset..offset + count)?;
+        let text = String::from_utf8_lossy(bytes);
+        Some(text.trim_end_matches('\0').to_string())
+    }
+
+    /// The (rows, columns) and sample values of the image described by
+    /// `ifd`, as one row major vector per band
+    pub(super) fn bands(&self, ifd: &Ifd) -> Result<(usize, usize, Vec<Vec<f32>>), Error> {
+        let cols = self.number(ifd, IMAGE_WIDTH, None)? as usize;
+        let rows = self.number(ifd, IMAGE_LENGTH, None)? as usize;
+        let spp = self.number(ifd, SAMPLES_PER_PIXEL, Some(1.))? as usize;
+        let planar = self.number(ifd, PLANAR_CONFIGURATION, Some(1.))? as usize;
+        let compression = self.number(ifd, COMPRESSION, Some(1.))? as usize;
+        let predictor = self.number(ifd, PREDICTOR, Some(1.))? as usize;
+
+        let bits = self.number(ifd, BITS_PER_SAMPLE, Some(1.))?;
+        let format = self.number(ifd, SAMPLE_FORMAT, Some(1.))?;
+        if bits != 32. || format != 3. {
+            return Err(unsupported("Only float32 samples are supported"));
+        }
+        if rows == 0 || cols == 0 || spp == 0 {
+            return Err(invalid("Empty image"));
+        }
+
+        // Strips are just tiles spanning the full width of the image
+        let tiled = ifd.entries.contains_key(&TILE_OFFSETS);
+        let (chunk_cols, chunk_rows, offsets, counts) = if tiled {
+            (
+                self.number(ifd, TILE_WIDTH, None)? as usize,
+                self.number(ifd, TILE_LENGTH, None)? as usize,
+                self.numbers(ifd, TILE_OFFSETS)?.unwrap_or_default(),
+                self.numbers(ifd, TILE_BYTE_COUNTS)?.unwrap_or_default(),
+            )
+        } else {
+            (
+                cols,
+                (self.number(ifd, ROWS_PER_STRIP, Some(rows as f64))? as usize).min(rows),
+                self.numbers(ifd, STRIP_OFFSETS)?.unwrap_or_default(),
+                self.numbers(ifd, STRIP_BYTE_COUNTS)?.unwrap_or_default(),
+            )
+        };
+        if chunk_cols == 0 || chunk_rows == 0 {
+            return Err(invalid("Bad tile or strip size"));
+        }
+
+        let across = (cols + chunk_cols - 1) / chunk_cols;
+        let down = (rows + chunk_rows - 1) / chunk_rows;
+        let (planes, samples) = if planar == 2 { (spp, 1) } else { (1, spp) };
+        let chunks = across.checked_mul(down).and_then(|n| n.checked_mul(planes));
+        let Some(chunks) = chunks.filter(|&n| n <= offsets.len() && n <= counts.len()) else {
+            return Err(invalid("Missing tiles or strips"));
+        };
+
+        // Check that the tiles or strips are inside the buffer, and that they
+        // can hold the image, before allocating room for it
+        let mut stored = 0_usize;
+        for (&offset, &count) in offsets.iter().zip(&counts).take(chunks) {
+            let end = (offset as usize).checked_add(count as usize);
+            if !end.is_some_and(|end| end <= self.buf.len()) {
+                return Err(invalid("Unexpected end of file"));
+            }
+            stored += count as usize;
+        }
+        let expansion = if compression == 1 { 1 } else { MAX_EXPANSION };
+        let size = rows
+            .checked_mul(cols)
+            .and_then(|n| n.checked_mul(spp))
+            .and_then(|n| n.checked_mul(4));
+        if !size.is_some_and(|size| size <= stored.saturating_mul(expansion)) {
+            return Err(invalid(
+                "Image size exceeds the size of its tiles or strips",
+            ));
+        }
+
+        let mut bands = vec![vec![0_f32; rows * cols]; spp];
+        for plane in 0..planes {
+            for chunk_row in 0..down {
+                for chunk_col in 0..across {
+                    let index = (plane * down + chunk_row) * across + chunk_col;
+                    let (offset, count) = (offsets[index] as usize, counts[index] as usize);
+                    let Some(raw) = self.buf.get(offset..offset + count) else {
+                        return Err(invalid("Unexpected end of file"));
+                    };
+                    let data = match compression {
+                        1 => raw.to_vec(),
+                        5 => lzw_decompress(raw)?,
+                        8 | 32946 => zlib_decompress(raw)?,
+                        _ => return Err(unsupported("Unsupported compression")),
+                    };
+
+                    // The last strip may be short, but tiles are always full size
+                    let first_row = chunk_row * chunk_rows;
+                    let height = if tiled {
+                        chunk_rows
+                    } else {
+                        chunk_rows.min(rows - first_row)
+                    };
+                    let row_size = chunk_cols * samples * 4;
+                    if data.len() < height * row_size {
+                        return Err(invalid("Short tile or strip"));
+                    }
+
+                    for r in 0..height {
+                        let row = first_row + r;
+                        if row >= rows {
+                            break;
+                        }
+                        let mut bytes = data[r * row_size..(r + 1) * row_size].to_vec();
+                        let values = self.decode_row(&mut bytes, samples, predictor)?;
+                        for c in 0..chunk_cols {
+                            let col = chunk_col * chunk_cols + c;
+                            if col >= cols {
+                                break;
+                            }
+                            for s in 0..samples {
+                                bands[plane + s][row * cols + col] = values[c * samples + s];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok((rows, cols, bands))
+    }
+
+    // Undo the predictor, and convert a row of bytes to f32 samples
+    fn decode_row(
+        &self,
+        bytes: &mut [u8],
+        stride: usize,
+        predictor: usize,
+    ) -> Result<Vec<f32>, Error> {
+        let n = bytes.len() / 4;
+        match predictor {
+            // No predictor
+            1 => Ok((0..n).map(|i| f32_at(bytes, i, self.big_endian)).collect()),
+
+            // Horizontal differencing of the 32 bit words
+            2 => {
+                let mut words: Vec<u32> =
+                    (0..n).map(|i| u32_at(bytes, i, self.big_endian)).collect();
+                for i in stride..n {
+                    words[i] = words[i].wrapping_add(words[i - stride]);
+                }
+                Ok(words.into_iter().map(f32::from_bits).collect())
+            }
+
+            // Floating point predictor: Byte-wise differencing, of the
+            // samples split into planes of big endian byte order
+            3 => {
+                for i in stride..bytes.len() {
+                    bytes[i] = bytes[i].wrapping_add(bytes[i - stride]);
+                }
+                Ok((0..n)
+                    .map(|i| {
+                        f32::from_be_bytes([
+                            bytes[i],
+                            bytes[n + i],
+                            bytes[2 * n + i],
+                            bytes[3 * n + i],
+                        ])
+                    })
+                    .collect())
+            }
+            _ => Err(unsupported("Unsupported predictor")),
+        }
+    }
+}
+
+fn u32_at(bytes: &[u8], i: usize, big_endian: bool) -> u32 {
+    let word: [u8; 4] = bytes[4 * i..4 * i + 4].try_into().unwrap();
+    if big_endian {
+        u32::from_be_bytes(word)
+    } else {
+        u32::from_le_bytes(word)
+    }
+}
+
+fn f32_at(bytes: &[u8], i: usize, big_endian: bool) -> f32 {
+    f32::from_bits(u32_at(bytes, i, big_endian))
+}
+
+// The size, in bytes, of each of the TIFF field types
+fn field_size(field_type: u16) -> usize {
+    match field_type {
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 1,
+    }
+}
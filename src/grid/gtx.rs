@@ -0,0 +1,106 @@
+//! Reader for the NOAA/NGS `.gtx` vertical grid format: A 40 byte
+//! big endian header, followed by the grid values as 32 bit floats, in
+//! south-to-north, west-to-east scan order.
+use super::{file_size, wrap_to_symmetric_range};
+use crate::Error;
+
+pub(super) const HEADER_SIZE: usize = 40;
+
+// The conventional nodata value, in meters
+pub(super) const NODATA: f32 = -88.8888;
+
+/// The plain header (in radians) and the number of rows and columns,
+/// from the first `HEADER_SIZE` bytes of a GTX file
+pub(super) fn gtx_header(buf: &[u8]) -> Result<([f64; 7], usize, usize), Error> {
+    if buf.len() < HEADER_SIZE {
+        return Err(Error::Invalid("GTX: Incomplete header".to_string()));
+    }
+    let f64_at = |i: usize| f64::from_be_bytes(buf[i..i + 8].try_into().unwrap());
+    let i32_at = |i: usize| i32::from_be_bytes(buf[i..i + 4].try_into().unwrap());
+
+    // Lower left corner, and grid spacing, in degrees
+    let lat_s = f64_at(0);
+    let lon_w = f64_at(8);
+    let dlat = f64_at(16);
+    let dlon = f64_at(24);
+    let rows = i32_at(32);
+    let cols = i32_at(36);
+
+    if rows < 2 || cols < 2 || dlat <= 0. || dlon <= 0. {
+        return Err(Error::Invalid("GTX: Malformed header".to_string()));
+    }
+    let (rows, cols) = (rows as usize, cols as usize);
+
+    let lat_n = lat_s + (rows - 1) as f64 * dlat;
+    let (lon_w, lon_e) = wrap_to_symmetric_range(lon_w, lon_w + (cols - 1) as f64 * dlon);
+
+    #[rustfmt::skip]
+    let header = [
+        lat_n.to_radians(), lat_s.to_radians(), lon_w.to_radians(), lon_e.to_radians(),
+        dlat.to_radians(), dlon.to_radians(), 1.,
+    ];
+    Ok((header, rows, cols))
+}
+
+/// Decode a single grid value
+pub(super) fn gtx_value(bytes: &[u8]) -> f32 {
+    f32::from_be_bytes(bytes[0..4].try_into().unwrap())
+}
+
+pub(super) fn gtx_grid_reader(buf: &[u8]) -> Result<([f64; 7], Vec<f32>), Error> {
+    let (header, rows, cols) = gtx_header(buf)?;
+    let Some(size) = file_size(HEADER_SIZE, 4, rows, cols) else {
+        return Err(Error::Invalid("GTX: Malformed header".to_string()));
+    };
+    if buf.len() < size {
+        return Err(Error::Invalid("GTX: Incomplete grid".to_string()));
+    }
+
+    // Reverse the row order, to get the north-to-south scan order
+    // expected by BaseGrid
+    let mut grid = Vec::with_capacity(rows * cols);
+    for row in (0..rows).rev() {
+        let start = HEADER_SIZE + 4 * row * cols;
+        for col in 0..cols {
+            grid.push(gtx_value(&buf[start + 4 * col..]));
+        }
+    }
+    Ok((header, grid))
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{BaseGrid, Grid};
+    use crate::prelude::*;
+
+    #[test]
+    fn gtx() -> Result<(), Error> {
+        // The Gravsoft test geoid, with a nodata value at the 54N 8E node
+        let buf = std::fs::read("geodesy/gtx/test.gtx")?;
+        let gtx = BaseGrid::gtx(&buf)?;
+        let gravsoft = BaseGrid::gravsoft(&std::fs::read("geodesy/geoid/test.geoid")?)?;
+        assert_eq!(gtx.bands(), 1);
+
+        for c in [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(57.5, 15.25, 0., 0.),
+        ] {
+            let expected = gravsoft.at(&c, 0.).unwrap();
+            assert!((gtx.at(&c, 0.).unwrap()[0] - expected[0]).abs() < 1e-5);
+        }
+
+        // Interpolation involving nodata nodes fails
+        let c = Coor4D::geo(54.5, 8.5, 0., 0.);
+        assert!(gtx.at(&c, 0.).is_none());
+
+        assert!(BaseGrid::gtx(&buf[0..100]).is_err());
+
+        // A corrupt header, claiming a huge grid
+        let mut corrupt = buf.clone();
+        corrupt[32..40].copy_from_slice(&[0x7f, 0xff, 0xff, 0xff, 0x7f, 0xff, 0xff, 0xff]);
+        assert!(matches!(BaseGrid::gtx(&corrupt), Err(Error::Invalid(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! Reader for the International Service for the Geoid `.isg` text format
+//! (ISG format versions 1.0 to 2.0): A `key : value` header, delimited by
+//! `begin_of_head` and `end_of_head` lines, followed by the grid values as
+//! whitespace separated numbers, row by row.
+use super::wrap_to_symmetric_range;
+use crate::Error;
+use std::collections::BTreeMap;
+
+fn malformed(what: &str) -> Error {
+    Error::Invalid(format!("ISG: {what}"))
+}
+
+pub(super) fn isg_grid_reader(buf: &[u8]) -> Result<([f64; 7], Vec<f32>), Error> {
+    let text = String::from_utf8_lossy(buf);
+    let Some((head, data)) = text.split_once("end_of_head") else {
+        return Err(Error::Unsupported("Not an ISG file".to_string()));
+    };
+    let Some((_, head)) = head.split_once("begin_of_head") else {
+        return Err(Error::Unsupported("Not an ISG file".to_string()));
+    };
+
+    // Header lines are `key : value` (ISG 1.0) or `key = value` (ISG 2.0)
+    let mut keys = BTreeMap::new();
+    for line in head.lines() {
+        if let Some(i) = line.find([':', '=']) {
+            let value = line[i + 1..].trim().to_string();
+            keys.insert(line[..i].trim().to_lowercase(), value);
+        }
+    }
+    let key = |k: &str| keys.get(k).map(|v| v.as_str()).unwrap_or_default();
+
+    if !matches!(key("coord type"), "" | "geodetic") {
+        return Err(Error::Unsupported(
+            "ISG: Only geodetic grids are supported".to_string(),
+        ));
+    }
+    let dms = key("coord units") == "dms";
+    let angle = |k: &str| parse_angle(key(k), dms).ok_or_else(|| malformed(k));
+    let lat_min = angle("lat min")?;
+    let lat_max = angle("lat max")?;
+    let lon_min = angle("lon min")?;
+    let lon_max = angle("lon max")?;
+    let dlat = angle("delta lat")?;
+    let dlon = angle("delta lon")?;
+    let count = |k: &str| key(k).parse::<usize>().map_err(|_| malformed(k));
+    let rows = count("nrows")?;
+    let cols = count("ncols")?;
+    let nodata = key("nodata").parse::<f64>().ok();
+
+    if rows < 2 || cols < 2 || dlat <= 0. || dlon <= 0. {
+        return Err(malformed("header"));
+    }
+
+    // The boundaries refer either to the outermost nodes, or to the outer
+    // edges of the outermost cells, in which case the nodes are half a cell
+    // inside the boundaries
+    let (mut lat_n, mut lat_s) = (lat_max, lat_min);
+    let (mut lon_w, mut lon_e) = (lon_min, lon_max);
+    if ((lat_max - lat_min) / dlat - rows as f64).abs() < 0.01 {
+        lat_n -= dlat / 2.;
+        lat_s += dlat / 2.;
+    }
+    if ((lon_max - lon_min) / dlon - cols as f64).abs() < 0.01 {
+        lon_w += dlon / 2.;
+        lon_e -= dlon / 2.;
+    }
+    let (lon_w, lon_e) = wrap_to_symmetric_range(lon_w, lon_e);
+
+    let mut grid = Vec::with_capacity(rows * cols);
+    for item in data
+        .split_whitespace()
+        .skip_while(|item| item.starts_with('='))
+    {
+        let value = item.parse::<f64>().map_err(|_| malformed("grid value"))?;
+        grid.push(if Some(value) == nodata {
+            f32::NAN
+        } else {
+            value as f32
+        });
+    }
+    if grid.len() != rows * cols {
+        return Err(malformed("grid size does not match the header"));
+    }
+
+    // BaseGrid expects north-to-south scan order
+    let ordering = key("data ordering").to_lowercase().replace(' ', "");
+    match ordering.as_str() {
+        "" | "n-to-s,w-to-e" => (),
+        "s-to-n,w-to-e" => {
+            let rows: Vec<_> = grid.chunks(cols).rev().flatten().copied().collect();
+            grid = rows;
+        }
+        _ => return Err(Error::Unsupported(format!("ISG: Data ordering {ordering}"))),
+    }
+
+    #[rustfmt::skip]
+    let header = [
+        lat_n.to_radians(), lat_s.to_radians(), lon_w.to_radians(), lon_e.to_radians(),
+        dlat.to_radians(), dlon.to_radians(), 1.,
+    ];
+    Ok((header, grid))
+}
+
+// Parse an angle given in decimal degrees, or (if `dms`) as e.g. 45°30'15"
+fn parse_angle(value: &str, dms: bool) -> Option<f64> {
+    if !dms {
+        return value.parse().ok();
+    }
+    let sign = if value.trim_start().starts_with('-') {
+        -1.
+    } else {
+        1.
+    };
+    let mut angle = 0.;
+    let parts = value
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .filter(|part| !part.is_empty());
+    for (part, unit) in parts.zip([1., 60., 3600.]) {
+        angle += part.parse::<f64>().ok()? / unit;
+    }
+    Some(sign * angle)
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{BaseGrid, Grid};
+    use crate::prelude::*;
+
+    #[test]
+    fn isg() -> Result<(), Error> {
+        // The Gravsoft test geoid, with a nodata value at the 54N 8E node
+        let buf = std::fs::read("geodesy/isg/test.isg")?;
+        let isg = BaseGrid::isg(&buf)?;
+        let gravsoft = BaseGrid::gravsoft(&std::fs::read("geodesy/geoid/test.geoid")?)?;
+        assert_eq!(isg.bands(), 1);
+
+        for c in [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(57.5, 15.25, 0., 0.),
+        ] {
+            let expected = gravsoft.at(&c, 0.).unwrap();
+            assert!((isg.at(&c, 0.).unwrap()[0] - expected[0]).abs() < 1e-5);
+        }
+
+        // Interpolation involving nodata nodes fails
+        let c = Coor4D::geo(54.5, 8.5, 0., 0.);
+        assert!(isg.at(&c, 0.).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn cell_registration_and_dms() -> Result<(), Error> {
+        // A 2x3 grid, with boundaries at the cell edges, given in dms
+        let isg = "begin_of_head\n\
+            lat min = 54°00'00\"\n lat max = 55°00'00\"\n\
+            lon min = 8°00'00\"\n lon max = 9°30'00\"\n\
+            delta lat = 0°30'00\"\n delta lon = 0°30'00\"\n\
+            nrows = 2\n ncols = 3\n coord units : dms\n\
+            end_of_head\n 1 2 3\n 4 5 6\n";
+        let (header, grid) = isg_grid_reader(isg.as_bytes())?;
+        let degrees: Vec<f64> = header[0..6].iter().map(|h| h.to_degrees()).collect();
+        let expected = [54.75, 54.25, 8.25, 9.25, 0.5, 0.5];
+        for i in 0..6 {
+            assert!((degrees[i] - expected[i]).abs() < 1e-12);
+        }
+        assert_eq!(grid, [1., 2., 3., 4., 5., 6.]);
+
+        let angle = parse_angle("-12°30'36\"", true).unwrap();
+        assert!((angle + 12.51).abs() < 1e-12);
+        assert!(isg_grid_reader(b"58.08 58.09").is_err());
+        Ok(())
+    }
+}
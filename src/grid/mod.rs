@@ -1,5 +1,10 @@
 //! Grid characteristics and interpolation.
 
+mod byn;
+pub mod file;
+pub mod geotiff;
+mod gtx;
+mod isg;
 pub mod ntv2;
 use crate::prelude::*;
 use std::{fmt::Debug, io::BufRead, sync::Arc};
@@ -14,6 +19,104 @@ pub trait Grid: Debug + Sync + Send {
     /// considered contained if it is inside a margin of `margin` grid units of
     /// the grid.
     fn at(&self, at: &Coor4D, margin: f64) -> Option<Coor4D>;
+    /// Like `at`, but using the interpolation method given by `method`.
+    /// The default implementation supports bilinear interpolation only,
+    /// and falls back to `at` for all methods
+    fn at_with(&self, at: &Coor4D, margin: f64, method: Interpolation) -> Option<Coor4D> {
+        let _ = method;
+        self.at(at, margin)
+    }
+    /// The horizontal accuracy of the grid values at `at`, if known, as its
+    /// east and north components, in metres. The default implementation
+    /// returns `None`
+    fn accuracy(&self, at: &Coor4D, margin: f64) -> Option<Coor4D> {
+        let _ = (at, margin);
+        None
+    }
+    /// Like `at_with`, but also returning the `accuracy` of the value, as
+    /// found in the same subgrid. The default implementation just calls both
+    fn at_with_accuracy(
+        &self,
+        at: &Coor4D,
+        margin: f64,
+        method: Interpolation,
+    ) -> Option<(Coor4D, Option<Coor4D>)> {
+        let value = self.at_with(at, margin, method)?;
+        Some((value, self.accuracy(at, margin)))
+    }
+    /// Description of the grid, or for grids consisting of a number of
+    /// subgrids, the tree of subgrids: The top level subgrids, each with its
+    /// children. The default implementation returns an empty vector
+    fn subgrids(&self) -> Vec<GridInfo> {
+        Vec::new()
+    }
+    /// The bounding box of the grid nodes, as `[north, south, west, east]`
+    /// (in radians for grids in angular units). For grids consisting of a
+    /// number of subgrids, the box enclosing the top level subgrids
+    fn extent(&self) -> Option<[f64; 4]> {
+        self.subgrids().iter().map(|g| g.extent).reduce(|a, b| {
+            [
+                a[0].max(b[0]),
+                a[1].min(b[1]),
+                a[2].min(b[2]),
+                a[3].max(b[3]),
+            ]
+        })
+    }
+    /// The (absolute) node spacing as `[dlat, dlon]` (in radians for grids in
+    /// angular units). For grids consisting of a number of subgrids, that of
+    /// the coarsest top level subgrid
+    fn resolution(&self) -> Option<[f64; 2]> {
+        self.subgrids()
+            .iter()
+            .map(|g| g.resolution)
+            .reduce(|a, b| [a[0].max(b[0]), a[1].max(b[1])])
+    }
+}
+
+/// Metadata describing a grid, or one of its subgrids, as returned by
+/// [`Grid::subgrids`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridInfo {
+    /// The subgrid name, for formats naming their subgrids (e.g. NTv2),
+    /// otherwise empty
+    pub name: String,
+    /// The bounding box of the grid nodes: `[north, south, west, east]`
+    pub extent: [f64; 4],
+    /// The absolute node spacing: `[dlat, dlon]`
+    pub resolution: [f64; 2],
+    pub rows: usize,
+    pub cols: usize,
+    pub bands: usize,
+    /// The subgrids nested inside this one
+    pub children: Vec<GridInfo>,
+}
+
+/// Grid interpolation methods
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Linear interpolation between the 2×2 nodes surrounding the point
+    #[default]
+    Bilinear,
+    /// Quadratic interpolation in the 3×3 nodes nearest to the point,
+    /// following NOAA Technical Memorandum NOS NGS 84, as in PROJ
+    Biquadratic,
+    /// Cubic convolution (Catmull-Rom) interpolation in the 4×4 nodes
+    /// surrounding the point. Nodes outside of the grid are replaced by
+    /// the nearest edge node
+    Bicubic,
+}
+
+impl std::str::FromStr for Interpolation {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "bilinear" => Ok(Interpolation::Bilinear),
+            "biquadratic" => Ok(Interpolation::Biquadratic),
+            "bicubic" => Ok(Interpolation::Bicubic),
+            _ => Err(Error::BadParam("interpolation".to_string(), s.to_string())),
+        }
+    }
 }
 
 /// Grid characteristics and interpolation.
@@ -36,6 +139,7 @@ pub struct BaseGrid {
     pub bands: usize,
     offset: usize,  // typically 0, but may be any number for externally stored grids
     grid: Vec<f32>, // May be zero sized in cases where the Context provides access to an externally stored grid
+    nodata: Option<f32>, // Sentinel value for nodes without data. NaN is always considered nodata
 }
 
 impl Grid for BaseGrid {
@@ -60,6 +164,11 @@ impl Grid for BaseGrid {
             return false;
         }
 
+        // Global grids are longitude periodic, so any longitude is inside
+        if self.periodic_columns().is_some() {
+            return true;
+        }
+
         // The default assumption is the other way round for columns (longitudes)
         min = self.lon_w;
         max = self.lon_e;
@@ -77,17 +186,49 @@ impl Grid for BaseGrid {
         true
     }
 
-    // Since we store the entire grid in a single vector, the interpolation
-    // routine here looks strongly like a case of "writing Fortran 77 in Rust".
-    // It is, however, one of the cases where a more extensive use of abstractions
-    // leads to a significantly larger code base, much harder to maintain and
-    // comprehend.
     fn at(&self, at: &Coor4D, margin: f64) -> Option<Coor4D> {
+        self.at_with(at, margin, Interpolation::Bilinear)
+    }
+
+    fn at_with(&self, at: &Coor4D, margin: f64, method: Interpolation) -> Option<Coor4D> {
+        let grid = &self.grid;
+        self.interpolate(at, margin, method, |index| Some(grid[index]))
+    }
+
+    fn subgrids(&self) -> Vec<GridInfo> {
+        vec![self.info("")]
+    }
+}
+
+impl BaseGrid {
+    /// Interpolation at `at`, reading the grid values through `value`, which
+    /// maps the index of a value in the (possibly externally stored) grid to
+    /// the value itself
+    pub(crate) fn interpolate(
+        &self,
+        at: &Coor4D,
+        margin: f64,
+        method: Interpolation,
+        mut value: impl FnMut(usize) -> Option<f32>,
+    ) -> Option<Coor4D> {
+        // Nodata nodes make the interpolation fail, so we can fall back to other grids
+        let nodata = self.nodata;
+        let mut value = |index| value(index).filter(|&v| !v.is_nan() && Some(v) != nodata);
+
+        // Since we store the entire grid in a single vector, the interpolation
+        // routine here looks strongly like a case of "writing Fortran 77 in Rust".
+        // It is, however, one of the cases where a more extensive use of abstractions
+        // leads to a significantly larger code base, much harder to maintain and
+        // comprehend.
         if !self.contains(at, margin) {
             return None;
         };
+        let at = &self.normalize_longitude(at);
 
-        let grid = &self.grid;
+        // Higher order methods need at least 3 nodes along each axis
+        if method != Interpolation::Bilinear && self.rows > 2 && self.cols > 2 {
+            return self.higher_order(at, method, value);
+        }
 
         // For now, we support top-to-bottom, left-to-right scan order only.
         // This is the common case for most non-block grid formats, with
@@ -106,16 +247,27 @@ impl Grid for BaseGrid {
         let row = (rlat / dlat).ceil() as i64;
         let col = (rlon / dlon).floor() as i64;
 
-        let col = col.clamp(0_i64, (self.cols - 2) as i64) as usize;
+        // For global grids, the column east of the last one is the first one
         let row = row.clamp(1_i64, (self.rows - 1) as i64) as usize;
+        let (col, west, east) = match self.periodic_columns() {
+            Some(period) => {
+                let period = period as i64;
+                let west = col.rem_euclid(period) as usize;
+                (col, west, (col + 1).rem_euclid(period) as usize)
+            }
+            None => {
+                let col = col.clamp(0_i64, (self.cols - 2) as i64);
+                (col, col as usize, col as usize + 1)
+            }
+        };
 
         // Index of the first band element of each corner value
         #[rustfmt::skip]
         let (ll, lr, ul, ur) = (
-            self.offset + self.bands * (self.cols *  row      + col    ),
-            self.offset + self.bands * (self.cols *  row      + col + 1),
-            self.offset + self.bands * (self.cols * (row - 1) + col    ),
-            self.offset + self.bands * (self.cols * (row - 1) + col + 1),
+            self.offset + self.bands * (self.cols *  row      + west),
+            self.offset + self.bands * (self.cols *  row      + east),
+            self.offset + self.bands * (self.cols * (row - 1) + west),
+            self.offset + self.bands * (self.cols * (row - 1) + east),
         );
 
         let ll_lon = self.lon_w + col as f64 * dlon;
@@ -132,14 +284,14 @@ impl Grid for BaseGrid {
 
         // Interpolate (or extrapolate, if we're outside of the physical grid)
         for i in 0..bands {
-            let lower = grid[ll + i] as f64;
-            let upper = grid[ul + i] as f64;
+            let lower = value(ll + i)? as f64;
+            let upper = value(ul + i)? as f64;
             left[i] = (1. - rlat) * lower + rlat * upper;
         }
         let mut right = Coor4D::origin();
         for i in 0..bands {
-            let lower = grid[lr + i] as f64;
-            let upper = grid[ur + i] as f64;
+            let lower = value(lr + i)? as f64;
+            let upper = value(ur + i)? as f64;
             right[i] = (1. - rlat) * lower + rlat * upper;
         }
 
@@ -150,9 +302,89 @@ impl Grid for BaseGrid {
 
         Some(result)
     }
-}
 
-impl BaseGrid {
+    // Biquadratic or bicubic interpolation: First along each of the rows of
+    // the interpolation window, then along the resulting column. Nodata nodes
+    // are already filtered out of `value` by `interpolate`
+    fn higher_order(
+        &self,
+        at: &Coor4D,
+        method: Interpolation,
+        mut value: impl FnMut(usize) -> Option<f32>,
+    ) -> Option<Coor4D> {
+        let n = if method == Interpolation::Biquadratic {
+            3
+        } else {
+            4
+        };
+
+        // Grid unit coordinates, relative to the north western node
+        let x = (at[0] - self.lon_w) / self.dlon.abs();
+        let y = (self.lat_n - at[1]) / self.dlat.abs();
+        let (cols, tx) = window(x, self.cols, self.periodic_columns(), method);
+        let (rows, ty) = window(y, self.rows, None, method);
+
+        let bands = self.bands.min(4);
+        let mut result = Coor4D::origin();
+        for band in 0..bands {
+            let mut column = [0_f64; 4];
+            for (r, &row) in rows.iter().take(n).enumerate() {
+                let mut nodes = [0_f64; 4];
+                for (c, &col) in cols.iter().take(n).enumerate() {
+                    let index = self.offset + self.bands * (self.cols * row + col) + band;
+                    nodes[c] = value(index)? as f64;
+                }
+                column[r] = polynomial(tx, &nodes, method);
+            }
+            result[band] = polynomial(ty, &column, method);
+        }
+        Some(result)
+    }
+
+    // Metadata for this grid, as a subgrid named `name`, without children
+    pub(crate) fn info(&self, name: &str) -> GridInfo {
+        GridInfo {
+            name: name.to_string(),
+            extent: [
+                self.lat_n.max(self.lat_s),
+                self.lat_n.min(self.lat_s),
+                self.lon_w.min(self.lon_e),
+                self.lon_w.max(self.lon_e),
+            ],
+            resolution: [self.dlat.abs(), self.dlon.abs()],
+            rows: self.rows,
+            cols: self.cols,
+            bands: self.bands,
+            children: Vec::new(),
+        }
+    }
+
+    // The number of distinct columns around the globe for grids covering the
+    // full circle of longitudes (with or without a duplicate column at the
+    // seam). None for grids with a more limited longitude coverage
+    fn periodic_columns(&self) -> Option<usize> {
+        let dlon = self.dlon.abs();
+        let period = (std::f64::consts::TAU / dlon).round();
+        if period < 2. || (period * dlon - std::f64::consts::TAU).abs() > 0.01 * dlon {
+            return None;
+        }
+        let period = period as usize;
+        if self.cols == period || self.cols == period + 1 {
+            return Some(period);
+        }
+        None
+    }
+
+    // For global grids, bring the longitude of `at` into the range covered
+    // by the columns of the grid, i.e. [lon_w; lon_w + 2π)
+    fn normalize_longitude(&self, at: &Coor4D) -> Coor4D {
+        let mut at = *at;
+        if self.periodic_columns().is_some() {
+            at[0] = self.lon_w + angular::normalize_positive(at[0] - self.lon_w);
+        }
+        at
+    }
+
     pub fn plain(
         header: &[f64],
         grid: Option<&[f32]>,
@@ -193,13 +425,132 @@ impl BaseGrid {
             bands,
             offset,
             grid,
+            nodata: None,
         })
     }
 
+    /// Set the sentinel value marking grid nodes without data. Interpolation
+    /// involving such nodes fails. NaN is always considered nodata
+    pub fn with_nodata(mut self, nodata: Option<f32>) -> Self {
+        self.nodata = nodata;
+        self
+    }
+
+    /// The sentinel value marking grid nodes without data, if any
+    pub fn nodata(&self) -> Option<f32> {
+        self.nodata
+    }
+
     pub fn gravsoft(buf: &[u8]) -> Result<Self, Error> {
         let (header, grid) = gravsoft_grid_reader(buf)?;
         BaseGrid::plain(&header, Some(&grid), None)
     }
+
+    /// Serialize the grid to Gravsoft format: The inverse of [`BaseGrid::gravsoft`],
+    /// hence with grid values in seconds of arc (2 bands, latitude first),
+    /// meters (1 band), or millimeters/year (3 bands, north first)
+    /// for grids in angular units. Nodata is written as 9999.
+    pub fn to_gravsoft(&self) -> Result<String, Error> {
+        let grid = &self.grid;
+        let nodes = self.rows * self.cols;
+        if self.offset + nodes * self.bands > grid.len() {
+            return Err(Error::General("Grid values not available"));
+        }
+
+        // Follow the heuristics of `normalize_gravsoft_grid_values`, which
+        // considers grids with boundaries outside of [-720; 720] linear
+        let header = [self.lat_s, self.lat_n, self.lon_w, self.lon_e];
+        let angular = header.iter().all(|h| h.to_degrees().abs() <= 720.);
+        let unit = |v: f64| if angular { v.to_degrees() } else { v };
+
+        // Round to avoid representation noise like 58.00000000000001
+        let round = |v: f64| (v * 1e10).round() / 1e10;
+        let mut out = format!(
+            "{} {} {} {} {} {}\n",
+            round(unit(self.lat_s)),
+            round(unit(self.lat_n)),
+            round(unit(self.lon_w)),
+            round(unit(self.lon_e)),
+            round(unit(self.dlat.abs())),
+            round(unit(self.dlon.abs())),
+        );
+
+        for row in 0..self.rows {
+            out += "\n";
+            let mut line = Vec::new();
+            for col in 0..self.cols {
+                let node = self.offset + self.bands * (row * self.cols + col);
+                let values = &grid[node..node + self.bands];
+                let mut node: Vec<f64> = values
+                    .iter()
+                    .map(|&v| {
+                        if Some(v) == self.nodata {
+                            f64::NAN
+                        } else {
+                            v as f64
+                        }
+                    })
+                    .collect();
+                if angular {
+                    match self.bands {
+                        2 => {
+                            node = vec![node[1].to_degrees() * 3600., node[0].to_degrees() * 3600.]
+                        }
+                        3 => node = vec![node[1] * 1000., node[0] * 1000., node[2] * 1000.],
+                        _ => (),
+                    }
+                }
+                line.extend(
+                    node.iter()
+                        .map(|&v| if v.is_nan() { GRAVSOFT_NODATA } else { v }),
+                );
+            }
+
+            // At most 8 values per line
+            for chunk in line.chunks(8) {
+                let chunk: Vec<_> = chunk.iter().map(|v| format!("{v:12.6}")).collect();
+                out += &chunk.join(" ");
+                out += "\n";
+            }
+        }
+        Ok(out)
+    }
+
+    /// Geoid model in the NOAA/NGS `.gtx` format
+    pub fn gtx(buf: &[u8]) -> Result<Self, Error> {
+        let (header, grid) = gtx::gtx_grid_reader(buf)?;
+        Ok(BaseGrid::plain(&header, Some(&grid), None)?.with_nodata(Some(gtx::NODATA)))
+    }
+
+    /// Geoid model in the Natural Resources Canada `.byn` format
+    pub fn byn(buf: &[u8]) -> Result<Self, Error> {
+        let (header, grid) = byn::byn_grid_reader(buf)?;
+        BaseGrid::plain(&header, Some(&grid), None)
+    }
+
+    /// Geoid model in the International Service for the Geoid `.isg` format
+    pub fn isg(buf: &[u8]) -> Result<Self, Error> {
+        let (header, grid) = isg::isg_grid_reader(buf)?;
+        BaseGrid::plain(&header, Some(&grid), None)
+    }
+}
+
+// Some formats give longitudes in the range [0; 360]. Shift grids entirely
+// east of the antimeridian to the symmetric range [-180; 180]
+fn wrap_to_symmetric_range(lon_w: f64, lon_e: f64) -> (f64, f64) {
+    if lon_w >= 180. {
+        return (lon_w - 360., lon_e - 360.);
+    }
+    (lon_w, lon_e)
+}
+
+// The size of a file consisting of a `header` of some bytes, followed by
+// `rows` x `cols` grid values of `size` bytes each. The dimensions may come
+// from a corrupt header, so we return None rather than overflow
+fn file_size(header: usize, size: usize, rows: usize, cols: usize) -> Option<usize> {
+    rows.checked_mul(cols)?
+        .checked_mul(size)?
+        .checked_add(header)
 }
 
 // If the Gravsoft grid appears to be in angular units, convert it to radians
@@ -248,7 +599,10 @@ fn normalize_gravsoft_grid_values(header: &mut [f64], grid: &mut [f32]) {
     }
 }
 
-// Read a gravsoft grid. Discard '#'-style comments
+// Gravsoft marks unknown grid values as 9999
+const GRAVSOFT_NODATA: f64 = 9999.;
+
+// Read a gravsoft grid. Discard '#'-style comments. Nodata values become NaN
 fn gravsoft_grid_reader(buf: &[u8]) -> Result<(Vec<f64>, Vec<f32>), Error> {
     let all = std::io::BufReader::new(buf);
     let mut grid = Vec::<f32>::new();
@@ -264,6 +618,8 @@ fn gravsoft_grid_reader(buf: &[u8]) -> Result<(Vec<f64>, Vec<f32>), Error> {
             // In Gravsoft grids, the header is the first 6 numbers of the file
             if header.len() < 6 {
                 header.push(value);
+            } else if value == GRAVSOFT_NODATA {
+                grid.push(f32::NAN);
             } else {
                 grid.push(value as f32);
             }
@@ -314,14 +670,88 @@ fn gravsoft_grid_reader(buf: &[u8]) -> Result<(Vec<f64>, Vec<f32>), Error> {
     Ok((header, grid))
 }
 
+// The indices of the nodes of the interpolation window along one axis, and
+// the position of `x` relative to the window (in node units): Relative to the
+// first node for biquadratic, and to the second for bicubic interpolation.
+// For a `period`ic axis, the window wraps around, rather than being limited
+// by the edges
+fn window(x: f64, nodes: usize, period: Option<usize>, method: Interpolation) -> ([usize; 4], f64) {
+    let last = nodes as i64 - 1;
+    if let Some(period) = period {
+        let index = |i: i64| i.rem_euclid(period as i64) as usize;
+        if method == Interpolation::Biquadratic {
+            let first = x.round() as i64 - 1;
+            return (
+                [
+                    index(first),
+                    index(first + 1),
+                    index(first + 2),
+                    index(first + 2),
+                ],
+                x - first as f64,
+            );
+        }
+        let base = x.floor() as i64;
+        return (
+            [
+                index(base - 1),
+                index(base),
+                index(base + 1),
+                index(base + 2),
+            ],
+            x - base as f64,
+        );
+    }
+
+    if method == Interpolation::Biquadratic {
+        // The 3 nodes centered at the nearest node - shifted inwards at the edges
+        let center = (x.round() as i64).clamp(1, (last - 1).max(1));
+        let first = center - 1;
+        let index = |i: i64| (first + i).clamp(0, last) as usize;
+        return ([index(0), index(1), index(2), index(2)], x - first as f64);
+    }
+
+    // The 4 nodes surrounding the cell containing x - replicating edge nodes
+    let base = (x.floor() as i64).clamp(0, (last - 1).max(0));
+    let index = |i: i64| (base + i).clamp(0, last) as usize;
+    ([index(-1), index(0), index(1), index(2)], x - base as f64)
+}
+
+// Evaluate the interpolating polynomial of the window nodes `f` at `t`
+fn polynomial(t: f64, f: &[f64; 4], method: Interpolation) -> f64 {
+    if method == Interpolation::Biquadratic {
+        // Newton's forward difference form, cf. eq. 8 of NOAA TM NOS NGS 84
+        let df0 = f[1] - f[0];
+        let df1 = f[2] - f[1];
+        return f[0] + t * df0 + 0.5 * t * (t - 1.) * (df1 - df0);
+    }
+
+    // Cubic convolution, with a = -0.5 (i.e. Catmull-Rom)
+    let (t2, t3) = (t * t, t * t * t);
+    0.5 * (2. * f[1]
+        + (f[2] - f[0]) * t
+        + (2. * f[0] - 5. * f[1] + 4. * f[2] - f[3]) * t2
+        + (3. * f[1] - f[0] - 3. * f[2] + f[3]) * t3)
+}
+
 /// Find the most appropriate grid value from a stack (i.e. slice) of grids.
 /// Search the grids in slice order and return the first hit.
 /// If no hits are found, try once more, this time adding a half grid-cell
 /// margin around each grid
 pub fn grids_at(grids: &[Arc<dyn Grid>], coord: &Coor4D, use_null_grid: bool) -> Option<Coor4D> {
+    grids_at_with(grids, coord, use_null_grid, Interpolation::Bilinear)
+}
+
+/// Like [`grids_at`], but using the interpolation method given by `method`
+pub fn grids_at_with(
+    grids: &[Arc<dyn Grid>],
+    coord: &Coor4D,
+    use_null_grid: bool,
+    method: Interpolation,
+) -> Option<Coor4D> {
     for margin in [0.0, 0.5] {
         for grid in grids.iter() {
-            let d = grid.at(coord, margin);
+            let d = grid.at_with(coord, margin, method);
             if d.is_some() {
                 return d;
             }
@@ -335,6 +765,31 @@ pub fn grids_at(grids: &[Arc<dyn Grid>], coord: &Coor4D, use_null_grid: bool) ->
     None
 }
 
+/// Like [`grids_at_with`], but also returning the accuracy of the value (see
+/// [`Grid::accuracy`]), if known, as found in the same grid. The null grid
+/// has no known accuracy
+pub fn grids_at_with_accuracy(
+    grids: &[Arc<dyn Grid>],
+    coord: &Coor4D,
+    use_null_grid: bool,
+    method: Interpolation,
+) -> Option<(Coor4D, Option<Coor4D>)> {
+    for margin in [0.0, 0.5] {
+        for grid in grids.iter() {
+            let d = grid.at_with_accuracy(coord, margin, method);
+            if d.is_some() {
+                return d;
+            }
+        }
+    }
+
+    if use_null_grid {
+        return Some((Coor4D::origin(), None));
+    }
+
+    None
+}
+
 // ----- T E S T S ------------------------------------------------------------------
 
 #[cfg(test)]
@@ -364,6 +819,188 @@ mod tests {
         54.08, 54.09, 54.10, 54.11, 54.12, 54.13, 54.14, 54.15, 54.16,
     ];
 
+    #[test]
+    fn interpolation() -> Result<(), Error> {
+        // A grid of a quadratic function of the grid unit coordinates,
+        // which biquadratic and bicubic interpolation reproduce exactly,
+        // while bilinear interpolation does not
+        let f = |x: f64, y: f64| x * x + 2. * y * y + x * y;
+        let mut grid = Vec::new();
+        for row in 0..5 {
+            for col in 0..9 {
+                grid.push(f(col as f64, (4 - row) as f64) as f32);
+            }
+        }
+        let header: Vec<f64> = [58., 54., 8., 16., 1., 1.]
+            .iter()
+            .map(|h: &f64| h.to_radians())
+            .chain([1.])
+            .collect();
+        let grid = BaseGrid::plain(&header, Some(&grid), None)?;
+
+        // Interior point, and points near the edges
+        for (lat, lon) in [(55.3, 12.7), (54.2, 8.1), (57.9, 15.6)] {
+            let c = Coor4D::geo(lat, lon, 0., 0.);
+            let expected = f(lon - 8., lat - 54.);
+            let bilinear = grid.at_with(&c, 0., Interpolation::Bilinear).unwrap()[0];
+            let biquadratic = grid.at_with(&c, 0., Interpolation::Biquadratic).unwrap()[0];
+            assert!((bilinear - expected).abs() > 1e-3);
+            assert!((biquadratic - expected).abs() < 1e-4);
+        }
+
+        // Bicubic (Catmull-Rom) is exact for quadratics away from the edges
+        let c = Coor4D::geo(55.3, 12.7, 0., 0.);
+        let bicubic = grid.at_with(&c, 0., Interpolation::Bicubic).unwrap()[0];
+        assert!((bicubic - f(4.7, 1.3)).abs() < 1e-4);
+
+        // ...and all methods agree at the nodes
+        let c = Coor4D::geo(56., 11., 0., 0.);
+        for method in ["bilinear", "biquadratic", "bicubic"] {
+            let v = grid.at_with(&c, 0., method.parse()?).unwrap()[0];
+            assert!((v - f(3., 2.)).abs() < 1e-5);
+        }
+        assert!("trilinear".parse::<Interpolation>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn global_grid() -> Result<(), Error> {
+        // Two global grids of 10 degree resolution, with 3 rows, and the
+        // value of each node being its column number. The first is given in
+        // the range 0..350, the second in -180..180, with a duplicate
+        // column at the seam
+        let mut grid = Vec::new();
+        for _ in 0..3 {
+            grid.extend((0..36).map(|col| col as f32));
+        }
+        let header: Vec<f64> = [1., -1., 0., 350., 1., 10.]
+            .iter()
+            .map(|h: &f64| h.to_radians())
+            .chain([1.])
+            .collect();
+        let positive = BaseGrid::plain(&header, Some(&grid), None)?;
+        assert_eq!(
+            positive.extent(),
+            Some([header[0], header[1], header[2], header[3]])
+        );
+        assert_eq!(positive.resolution(), Some([header[4], header[5]]));
+        assert_eq!(
+            (positive.subgrids()[0].rows, positive.subgrids()[0].cols),
+            (3, 36)
+        );
+
+        let mut grid = Vec::new();
+        for _ in 0..3 {
+            grid.extend((0..37).map(|col| ((col + 18) % 36) as f32));
+        }
+        let header: Vec<f64> = [1., -1., -180., 180., 1., 10.]
+            .iter()
+            .map(|h: &f64| h.to_radians())
+            .chain([1.])
+            .collect();
+        let symmetric = BaseGrid::plain(&header, Some(&grid), None)?;
+
+        // Bilinear interpolation across the seam of the positive grid...
+        for lon in [-5., 355., 715.] {
+            let c = Coor4D::geo(0., lon, 0., 0.);
+            assert!(positive.contains(&c, 0.));
+            assert!((positive.at(&c, 0.).unwrap()[0] - 17.5).abs() < 1e-10);
+        }
+
+        // ...and across the antimeridian of the symmetric one
+        let c = Coor4D::geo(0., 185., 0., 0.);
+        assert!((symmetric.at(&c, 0.).unwrap()[0] - 18.5).abs() < 1e-10);
+
+        // Both grids represent the same field, so the interpolated values
+        // should agree everywhere, for all interpolation methods
+        for method in ["bilinear", "biquadratic", "bicubic"] {
+            for lon in [-178., -5., 0.5, 3., 179.9, 183., 357.] {
+                let c = Coor4D::geo(0.3, lon, 0., 0.);
+                let method = method.parse()?;
+                let p = positive.at_with(&c, 0., method).unwrap()[0];
+                let s = symmetric.at_with(&c, 0., method).unwrap()[0];
+                assert!((p - s).abs() < 1e-10);
+            }
+        }
+
+        // Non-global grids are not periodic
+        let c = Coor4D::geo(55., 372., 0., 0.);
+        let grid = BaseGrid::gravsoft(&std::fs::read("geodesy/datum/test.datum")?)?;
+        assert!(!grid.contains(&c, 0.5));
+        Ok(())
+    }
+
+    #[test]
+    fn nodata() -> Result<(), Error> {
+        // A 3x4 grid with a hole at 55N 9E, marked by a sentinel, a grid
+        // without holes, and a grid of NaN
+        let header: Vec<f64> = [56., 54., 8., 11., 1., 1.]
+            .iter()
+            .map(|h: &f64| h.to_radians())
+            .chain([1.])
+            .collect();
+        let mut values = [1_f32; 12];
+        values[5] = -99.;
+        let holey = BaseGrid::plain(&header, Some(&values), None)?.with_nodata(Some(-99.));
+        assert_eq!(holey.nodata(), Some(-99.));
+        let holey: Arc<dyn Grid> = Arc::new(holey);
+        let full: Arc<dyn Grid> = Arc::new(BaseGrid::plain(&header, Some(&[2.; 12]), None)?);
+        let nan: Arc<dyn Grid> = Arc::new(BaseGrid::plain(&header, Some(&[f32::NAN; 12]), None)?);
+
+        // Interpolation involving the hole fails...
+        let c = Coor4D::geo(54.5, 8.5, 0., 0.);
+        for method in ["bilinear", "biquadratic", "bicubic"] {
+            assert!(holey.at_with(&c, 0., method.parse()?).is_none());
+        }
+        assert!(holey.at(&Coor4D::geo(55.5, 10.5, 0., 0.), 0.).is_some());
+
+        // ...so grids_at falls back to the next grid
+        let grids = [holey.clone(), full];
+        assert_eq!(grids_at(&grids, &c, false).unwrap()[0], 2.);
+        let c = Coor4D::geo(55.5, 10.5, 0., 0.);
+        assert_eq!(grids_at(&grids, &c, false).unwrap()[0], 1.);
+
+        // NaN is always nodata
+        let c = Coor4D::geo(54.5, 8.5, 0., 0.);
+        for method in ["bilinear", "biquadratic", "bicubic"] {
+            assert!(nan.at_with(&c, 0., method.parse()?).is_none());
+        }
+        let grids = [holey, nan];
+        assert!(grids_at(&grids, &c, false).is_none());
+        assert_eq!(grids_at(&grids, &c, true).unwrap()[0], 0.);
+
+        // Gravsoft marks nodata as 9999, which becomes NaN, and is written back as 9999
+        let gravsoft = "54 56 8 11 1 1\n 1 1 1 1\n 1 9999 1 1\n 1 1 1 1\n";
+        let grid = BaseGrid::gravsoft(gravsoft.as_bytes())?;
+        assert!(grid.at(&c, 0.).is_none());
+        let c = Coor4D::geo(55.5, 10.5, 0., 0.);
+        assert_eq!(grid.at(&c, 0.).unwrap()[0], 1.);
+        assert!(grid.to_gravsoft()?.contains("9999"));
+        Ok(())
+    }
+
+    #[test]
+    fn gravsoft_roundtrip() -> Result<(), Error> {
+        for name in [
+            "datum/test.datum",
+            "geoid/test.geoid",
+            "deformation/test.deformation",
+        ] {
+            let original = BaseGrid::gravsoft(&std::fs::read(format!("geodesy/{name}"))?)?;
+            let text = original.to_gravsoft()?;
+            let copy = BaseGrid::gravsoft(text.as_bytes())?;
+            assert_eq!(copy.bands, original.bands);
+            assert_eq!((copy.rows, copy.cols), (original.rows, original.cols));
+            assert!((copy.lat_n - original.lat_n).abs() < 1e-14);
+            assert!((copy.lon_e - original.lon_e).abs() < 1e-14);
+            assert!((copy.dlat - original.dlat).abs() < 1e-14);
+            for (a, b) in copy.grid.iter().zip(&original.grid) {
+                assert!((a - b).abs() < 1e-6 * b.abs().max(1e-5));
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn grid_header() -> Result<(), Error> {
         // Create a datum correction grid (2 bands)
@@ -1,9 +1,15 @@
 mod parser;
 mod subgrid;
+mod writer;
 
-use self::subgrid::NODE_SIZE;
+use self::subgrid::{NODATA, NODE_SIZE};
 use super::BaseGrid;
-use crate::{coord::Coor4D, grid::Grid, Error};
+use crate::{
+    coord::Coor4D,
+    ellipsoid::{biaxial::Ellipsoid, EllipsoidBase},
+    grid::{Grid, GridInfo, Interpolation},
+    Error,
+};
 use parser::{NTv2Parser, HEADER_SIZE};
 use std::collections::BTreeMap;
 
@@ -13,6 +19,10 @@ pub struct Ntv2Grid {
     // Subgrids stored by their `SUBNAME` property
     subgrids: BTreeMap<String, BaseGrid>,
 
+    // Longitude and latitude accuracies of the subgrids, in seconds of arc,
+    // and stored by their `SUBNAME` property. NaN where unknown
+    accuracies: BTreeMap<String, BaseGrid>,
+
     // Lookup table for finding subgrids by their `PARENT` property
     // The key is the `PARENT` property and the value is a vector of `SUBNAME` properties
     // It's expected that root subgrids have a `PARENT` property of `NONE`
@@ -42,17 +52,19 @@ impl Ntv2Grid {
         let num_sub_grids = parser.get_u32(40) as usize;
 
         let mut subgrids = BTreeMap::new();
+        let mut accuracies = BTreeMap::new();
         let mut lookup_table = BTreeMap::new();
 
         let mut offset = HEADER_SIZE;
         for _ in 0..num_sub_grids {
-            let (name, parent, grid) = subgrid::ntv2_subgrid(&parser, offset)?;
+            let (name, parent, grid, accuracy) = subgrid::ntv2_subgrid(&parser, offset)?;
             offset += HEADER_SIZE + grid.grid.len() / 2 * NODE_SIZE;
 
             // The NTv2 spec does not guarantee the order of subgrids, so we must create
             // a lookup table from parent to children to make it possible for `find_grid` to
             // have a start point for working out which subgrid, if any, contains the point
             subgrids.insert(name.clone(), grid);
+            accuracies.insert(name.clone(), accuracy);
             lookup_table
                 .entry(parent)
                 .or_insert_with(Vec::new)
@@ -61,6 +73,7 @@ impl Ntv2Grid {
 
         Ok(Self {
             subgrids,
+            accuracies,
             lookup_table,
         })
     }
@@ -121,6 +134,22 @@ impl Ntv2Grid {
         // None of the subgrids contain the point
         None
     }
+
+    // The metadata of the children of `parent`, including their descendants
+    fn subgrid_tree(&self, parent: &str) -> Vec<GridInfo> {
+        let Some(children) = self.lookup_table.get(parent) else {
+            return Vec::new();
+        };
+        let mut tree = Vec::new();
+        for name in children {
+            if let Some(grid) = self.subgrids.get(name) {
+                let mut info = grid.info(name);
+                info.children = self.subgrid_tree(name);
+                tree.push(info);
+            }
+        }
+        tree
+    }
 }
 
 impl Grid for Ntv2Grid {
@@ -137,6 +166,49 @@ impl Grid for Ntv2Grid {
         self.find_grid(coord, margin)
             .and_then(|grid| grid.1.at(coord, margin))
     }
+
+    fn at_with(&self, coord: &Coor4D, margin: f64, method: Interpolation) -> Option<Coor4D> {
+        self.find_grid(coord, margin)
+            .and_then(|grid| grid.1.at_with(coord, margin, method))
+    }
+
+    /// The subgrid hierarchy, following the `PARENT` properties
+    fn subgrids(&self) -> Vec<GridInfo> {
+        self.subgrid_tree("NONE")
+    }
+
+    /// The accuracies of the NTv2 subgrid used at `coord`
+    fn accuracy(&self, coord: &Coor4D, margin: f64) -> Option<Coor4D> {
+        let (name, _) = self.find_grid(coord, margin)?;
+        self.accuracy_in(&name, coord, margin)
+    }
+
+    fn at_with_accuracy(
+        &self,
+        coord: &Coor4D,
+        margin: f64,
+        method: Interpolation,
+    ) -> Option<(Coor4D, Option<Coor4D>)> {
+        let (name, grid) = self.find_grid(coord, margin)?;
+        let value = grid.at_with(coord, margin, method)?;
+        Some((value, self.accuracy_in(&name, coord, margin)))
+    }
+}
+
+impl Ntv2Grid {
+    // The accuracies of the subgrid `name` at `coord`, converted from seconds
+    // of arc to metres. Since they are only given to a few significant digits,
+    // the choice of ellipsoid for the conversion is immaterial, so we use GRS80
+    fn accuracy_in(&self, name: &str, coord: &Coor4D, margin: f64) -> Option<Coor4D> {
+        let accuracy = self.accuracies.get(name)?.at(coord, margin)?;
+        let ellps = Ellipsoid::default();
+        let lat = coord[1];
+        let east = ellps.prime_vertical_radius_of_curvature(lat) * lat.cos();
+        let north = ellps.meridian_radius_of_curvature(lat);
+        let east = east * (accuracy[0] / 3600.).to_radians();
+        let north = north * (accuracy[1] / 3600.).to_radians();
+        Some(Coor4D([east, north, 0., 0.]))
+    }
 }
 
 // ----- T E S T S ---------------------------------------------------------------------
@@ -193,6 +265,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn ntv2_accuracy() -> Result<(), Error> {
+        // 100800401.gsb gives all accuracies as -1, i.e. unknown
+        let grid = Ntv2Grid::new(&std::fs::read("geodesy/gsb/100800401.gsb")?)?;
+        let barc = Coor4D::geo(41.3874, 2.1686, 0.0, 0.0);
+        assert!(grid.at(&barc, 0.).is_some());
+        assert!(grid.accuracy(&barc, 0.).is_none());
+
+        // 5458_accuracy.gsb is 5458.gsb, with latitude and longitude
+        // accuracies of 0.03 and 0.04 seconds of arc at all nodes, i.e.
+        // 0.7022 m east and 0.9278 m north at 55.5°N, on GRS80
+        let grid = Ntv2Grid::new(&std::fs::read("geodesy/gsb/5458_accuracy.gsb")?)?;
+        let c = Coor4D::geo(55.5, 12.25, 0.0, 0.0);
+        let accuracy = grid.accuracy(&c, 0.).unwrap();
+        assert_float_eq!(accuracy[0], 0.7022, abs_all <= 1e-4);
+        assert_float_eq!(accuracy[1], 0.9278, abs_all <= 1e-4);
+        let (value, with) = grid
+            .at_with_accuracy(&c, 0., Interpolation::Bilinear)
+            .unwrap();
+        assert_eq!(Some(value), grid.at(&c, 0.));
+        assert_eq!(with, Some(accuracy));
+        assert!(grid.accuracy(&Coor4D::geo(41., 2., 0., 0.), 0.).is_none());
+        Ok(())
+    }
+
     #[test]
     fn ntv2_multi_subgrid() -> Result<(), Error> {
         let grid_buff = std::fs::read("geodesy/gsb/5458_with_subgrid.gsb").unwrap();
@@ -209,6 +306,34 @@ mod tests {
         // Grids with no children do not appear in the lookup table
         assert!(!ntv2_grid.lookup_table.contains_key("5556"));
 
+        // The subgrid tree: 5458 covering 54-58N, 8-16E, at 1 degree
+        // resolution, with a nested subgrid 5556 at half degree resolution
+        let tree = ntv2_grid.subgrids();
+        assert_eq!(tree.len(), 1);
+        let (root, child) = (&tree[0], &tree[0].children[0]);
+        assert_eq!((root.name.as_str(), child.name.as_str()), ("5458", "5556"));
+        assert_eq!((root.rows, root.cols, root.bands), (5, 9, 2));
+        assert_eq!((child.rows, child.cols, child.bands), (3, 5, 2));
+        assert!(child.children.is_empty());
+
+        let degrees = |v: &[f64]| v.iter().map(|v| v.to_degrees()).collect::<Vec<_>>();
+        for (a, b) in degrees(&ntv2_grid.extent().unwrap())
+            .iter()
+            .zip([58., 54., 8., 16.])
+        {
+            assert_float_eq!(*a, b, abs <= 1e-9);
+        }
+        for (a, b) in degrees(&child.extent).iter().zip([56., 55., 12., 14.]) {
+            assert_float_eq!(*a, b, abs <= 1e-9);
+        }
+        for (a, b) in degrees(&ntv2_grid.resolution().unwrap())
+            .iter()
+            .zip([1., 1.])
+        {
+            assert_float_eq!(*a, b, abs <= 1e-9);
+        }
+        assert_float_eq!(child.resolution[0].to_degrees(), 0.5, abs <= 1e-9);
+
         Ok(())
     }
 
@@ -1,18 +1,20 @@
 use super::*;
 
+// Returns the name, parent, shift grid, and accuracy grid of the subgrid
 pub(super) fn ntv2_subgrid(
     parser: &NTv2Parser,
     head_offset: usize,
-) -> Result<(String, String, BaseGrid), Error> {
+) -> Result<(String, String, BaseGrid, BaseGrid), Error> {
     let head = SubGridHeader::new(parser, head_offset)?;
     let name = head.name.clone();
     let parent = head.parent.clone();
 
     let grid_start = head_offset + HEADER_SIZE;
-    let grid = parse_subgrid_grid(parser, grid_start, head.num_nodes as usize)?;
+    let (grid, accuracy) = parse_subgrid_grid(parser, grid_start, head.num_nodes as usize)?;
     let header = head.into_header();
     let base_grid = BaseGrid::plain(&header, Some(&grid), Some(0))?;
-    Ok((name, parent, base_grid))
+    let accuracy_grid = BaseGrid::plain(&header, Some(&accuracy), Some(0))?;
+    Ok((name, parent, base_grid, accuracy_grid))
 }
 
 // Buffer offsets for the NTv2 subgrid header
@@ -84,33 +86,52 @@ impl SubGridHeader {
 // Buffer offsets for the NTv2 grid nodes
 const NODE_LAT_CORRECTION: usize = 0;
 const NODE_LON_CORRECTION: usize = 4;
+const NODE_LAT_ACCURACY: usize = 8;
+const NODE_LON_ACCURACY: usize = 12;
 pub(super) const NODE_SIZE: usize = 16;
 
-// Parse the nodes of a sub grid into a vector of lon/lat shifts in radians
+// The conventional filler for nodes without data, in seconds of arc
+pub(super) const NODATA: f32 = -9999.;
+
+// Parse the nodes of a sub grid into a vector of lon/lat shifts in radians,
+// and a vector of lon/lat accuracies, in the units of the file. Nodes without
+// data, and negative (i.e. unknown) accuracies, become NaN
 fn parse_subgrid_grid(
     parser: &NTv2Parser,
     grid_start: usize,
     num_nodes: usize,
-) -> Result<Vec<f32>, Error> {
+) -> Result<(Vec<f32>, Vec<f32>), Error> {
     let grid_end_offset = grid_start + num_nodes * NODE_SIZE;
     if grid_end_offset > parser.buffer().len() {
         return Err(Error::Invalid("Grid Too Short".to_string()));
     }
 
     let mut grid = Vec::with_capacity(2 * num_nodes);
+    let mut accuracy = Vec::with_capacity(2 * num_nodes);
     for i in 0..num_nodes {
         let offset = grid_start + i * NODE_SIZE;
         let lat_offset = offset + NODE_LAT_CORRECTION;
         let lon_offset = offset + NODE_LON_CORRECTION;
 
-        let mut lat_corr = parser.get_f32(lat_offset) as f64;
-        let mut lon_corr = -parser.get_f32(lon_offset) as f64;
-        lat_corr = (lat_corr / 3600.).to_radians();
-        lon_corr = (lon_corr / 3600.).to_radians();
+        for accuracy_offset in [offset + NODE_LAT_ACCURACY, offset + NODE_LON_ACCURACY] {
+            let value = parser.get_f32(accuracy_offset);
+            accuracy.push(if value < 0. { f32::NAN } else { value });
+        }
+
+        let lat_corr = parser.get_f32(lat_offset);
+        let lon_corr = parser.get_f32(lon_offset);
+        if lat_corr == NODATA || lon_corr == NODATA {
+            grid.push(f32::NAN);
+            grid.push(f32::NAN);
+            continue;
+        }
+        let lat_corr = (lat_corr as f64 / 3600.).to_radians();
+        let lon_corr = (-lon_corr as f64 / 3600.).to_radians();
         grid.push(lat_corr as f32);
         grid.push(lon_corr as f32);
     }
     grid.reverse();
+    accuracy.reverse();
 
-    Ok(grid)
+    Ok((grid, accuracy))
 }
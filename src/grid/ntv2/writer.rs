@@ -0,0 +1,256 @@
+use super::*;
+
+// Node accuracies of -1 mark the accuracy as unknown: Two little endian -1_f32
+const UNKNOWN_ACCURACY: [u8; 8] = [0x00, 0x00, 0x80, 0xbf, 0x00, 0x00, 0x80, 0xbf];
+
+// A subgrid to write: Name, parent, shift grid, and accuracy grid (if known)
+type Subgrid<'a> = (&'a str, &'a str, &'a BaseGrid, Option<&'a BaseGrid>);
+
+// The node ordering of NTv2 is the reverse of the BaseGrid ordering: Starting
+// at the south eastern corner, proceeding westward along each row, then northward
+fn write_subgrid(out: &mut Vec<u8>, subgrid: &Subgrid) -> Result<(), Error> {
+    let &(name, parent, grid, accuracy) = subgrid;
+    if grid.bands != 2 {
+        return Err(Error::Unsupported(
+            "NTv2: Only 2 band grids can be written".to_string(),
+        ));
+    }
+    if grid.lat_n <= grid.lat_s || grid.lon_e <= grid.lon_w {
+        return Err(Error::Unsupported(
+            "NTv2: Grid must be in north-to-south, west-to-east scan order".to_string(),
+        ));
+    }
+    if !has_values(grid) {
+        return Err(Error::General("Grid values not available"));
+    }
+    if let Some(accuracy) = accuracy {
+        let same_shape =
+            (accuracy.rows, accuracy.cols, accuracy.bands) == (grid.rows, grid.cols, 2);
+        if !same_shape || !has_values(accuracy) {
+            return Err(Error::Invalid(format!(
+                "NTv2: Accuracy grid does not match the shift grid of '{name}'"
+            )));
+        }
+    }
+
+    // Header values are in seconds of arc, with longitudes positive west.
+    // We round to the nearest microsecond, to avoid off-by-one errors when
+    // the grid dimensions are recomputed from the header
+    let seconds = |radians: f64| (radians.to_degrees() * 3600. * 1e6).round() / 1e6;
+    let nodes = grid.rows * grid.cols;
+
+    record(out, "SUB_NAME", &text(name)?);
+    record(out, "PARENT", &text(parent)?);
+    record(out, "CREATED", &text("")?);
+    record(out, "UPDATED", &text("")?);
+    record(out, "S_LAT", &seconds(grid.lat_s).to_le_bytes());
+    record(out, "N_LAT", &seconds(grid.lat_n).to_le_bytes());
+    record(out, "E_LONG", &seconds(-grid.lon_e).to_le_bytes());
+    record(out, "W_LONG", &seconds(-grid.lon_w).to_le_bytes());
+    record(out, "LAT_INC", &seconds(grid.dlat.abs()).to_le_bytes());
+    record(out, "LONG_INC", &seconds(grid.dlon.abs()).to_le_bytes());
+    record(out, "GS_COUNT", &integer(nodes));
+
+    for i in (0..nodes).rev() {
+        let index = grid.offset + 2 * i;
+        let lon = grid.grid[index];
+        let lat = grid.grid[index + 1];
+        if lon.is_nan() || lat.is_nan() || grid.nodata.is_some_and(|n| n == lon || n == lat) {
+            out.extend(NODATA.to_le_bytes());
+            out.extend(NODATA.to_le_bytes());
+            out.extend(UNKNOWN_ACCURACY);
+            continue;
+        }
+        out.extend((((lat as f64).to_degrees() * 3600.) as f32).to_le_bytes());
+        out.extend(((-(lon as f64).to_degrees() * 3600.) as f32).to_le_bytes());
+
+        // Accuracies, if known. NaN (unknown) is written as -1
+        let Some(accuracy) = accuracy else {
+            out.extend(UNKNOWN_ACCURACY);
+            continue;
+        };
+        let index = accuracy.offset + 2 * i;
+        for value in [accuracy.grid[index + 1], accuracy.grid[index]] {
+            let value = if value.is_nan() { -1. } else { value };
+            out.extend(value.to_le_bytes());
+        }
+    }
+    Ok(())
+}
+
+// Are the values of the 2 band grid `grid` all in memory?
+fn has_values(grid: &BaseGrid) -> bool {
+    grid.rows
+        .checked_mul(grid.cols)
+        .and_then(|nodes| nodes.checked_mul(2))
+        .and_then(|values| values.checked_add(grid.offset))
+        .is_some_and(|end| end <= grid.grid.len())
+}
+
+// Overview header, subgrids, and end record
+fn write_file(subgrids: &[Subgrid]) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::new();
+    record(&mut out, "NUM_OREC", &integer(11));
+    record(&mut out, "NUM_SREC", &integer(11));
+    record(&mut out, "NUM_FILE", &integer(subgrids.len()));
+    record(&mut out, "GS_TYPE", &text("SECONDS")?);
+    record(&mut out, "VERSION", &text("NTv2.0")?);
+    // The reference systems and ellipsoids are not known to us
+    record(&mut out, "SYSTEM_F", &text("")?);
+    record(&mut out, "SYSTEM_T", &text("")?);
+    record(&mut out, "MAJOR_F", &0_f64.to_le_bytes());
+    record(&mut out, "MINOR_F", &0_f64.to_le_bytes());
+    record(&mut out, "MAJOR_T", &0_f64.to_le_bytes());
+    record(&mut out, "MINOR_T", &0_f64.to_le_bytes());
+
+    for subgrid in subgrids {
+        write_subgrid(&mut out, subgrid)?;
+    }
+    record(&mut out, "END", &[0_u8; 8]);
+    Ok(out)
+}
+
+// A 16 byte header record: An 8 character key, followed by an 8 byte value
+fn record(out: &mut Vec<u8>, key: &str, value: &[u8; 8]) {
+    out.extend(format!("{key:<8}").bytes());
+    out.extend(value);
+}
+
+fn text(value: &str) -> Result<[u8; 8], Error> {
+    let value = format!("{value:<8}");
+    value
+        .as_bytes()
+        .try_into()
+        .map_err(|_| Error::Invalid(format!("NTv2: Name too long: '{value}'")))
+}
+
+fn integer(value: usize) -> [u8; 8] {
+    let mut result = [0_u8; 8];
+    result[0..4].copy_from_slice(&(value as u32).to_le_bytes());
+    result
+}
+
+impl Ntv2Grid {
+    /// Serialize the grid, including all subgrids, to (little endian) NTv2 format.
+    /// Parents are written before their children, as required by some readers.
+    pub fn to_ntv2(&self) -> Result<Vec<u8>, Error> {
+        let mut subgrids = Vec::new();
+        let mut parents = vec!["NONE".to_string()];
+        while let Some(parent) = parents.pop() {
+            let Some(children) = self.lookup_table.get(&parent) else {
+                continue;
+            };
+            for child in children {
+                let grid = self.subgrids.get(child).unwrap();
+                subgrids.push((child.clone(), parent.clone(), grid));
+                parents.push(child.clone());
+            }
+        }
+        let subgrids: Vec<_> = subgrids
+            .iter()
+            .map(|(name, parent, grid)| {
+                let accuracy = self.accuracies.get(name);
+                (name.as_str(), parent.as_str(), *grid, accuracy)
+            })
+            .collect();
+        write_file(&subgrids)
+    }
+}
+
+impl BaseGrid {
+    /// Serialize a 2 band (horizontal datum shift) grid to (little endian)
+    /// NTv2 format, as a single subgrid named `name`. Combined with
+    /// [`BaseGrid::gravsoft`], this converts Gravsoft datum grids to NTv2.
+    pub fn to_ntv2(&self, name: &str) -> Result<Vec<u8>, Error> {
+        write_file(&[(name, "NONE", self, None)])
+    }
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_same_grid(a: &BaseGrid, b: &BaseGrid) {
+        for (x, y) in [
+            (a.lat_n, b.lat_n),
+            (a.lat_s, b.lat_s),
+            (a.lon_w, b.lon_w),
+            (a.lon_e, b.lon_e),
+            (a.dlat, b.dlat),
+            (a.dlon, b.dlon),
+        ] {
+            assert!((x - y).abs() < 1e-14);
+        }
+        assert_eq!(a.grid.len(), b.grid.len());
+        for (x, y) in a.grid.iter().zip(&b.grid) {
+            // One microsecond of arc
+            assert!((x - y).abs() < 5e-12);
+        }
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let buf = std::fs::read("geodesy/gsb/5458_with_subgrid.gsb")?;
+        let original = Ntv2Grid::new(&buf)?;
+        let copy = Ntv2Grid::new(&original.to_ntv2()?)?;
+
+        assert_eq!(copy.lookup_table, original.lookup_table);
+        assert_eq!(copy.subgrids.len(), 2);
+        for (name, grid) in &original.subgrids {
+            assert_same_grid(grid, copy.subgrids.get(name).unwrap());
+        }
+
+        // The densified subgrid is still selected where appropriate
+        let c = Coor4D::geo(55.5, 13.0, 0.0, 0.0);
+        assert_eq!(copy.find_grid(&c, 1e-6).unwrap().0, "5556");
+        assert_eq!(copy.at(&c, 0.), original.at(&c, 0.));
+
+        // Accuracies are retained
+        let buf = std::fs::read("geodesy/gsb/5458_accuracy.gsb")?;
+        let original = Ntv2Grid::new(&buf)?;
+        let copy = Ntv2Grid::new(&original.to_ntv2()?)?;
+        assert_eq!(copy.accuracy(&c, 0.), original.accuracy(&c, 0.));
+        assert!(copy.accuracy(&c, 0.).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn gravsoft_to_ntv2() -> Result<(), Error> {
+        let buf = std::fs::read("geodesy/datum/test.datum")?;
+        let gravsoft = BaseGrid::gravsoft(&buf)?;
+        let ntv2 = Ntv2Grid::new(&gravsoft.to_ntv2("TEST")?)?;
+        assert_same_grid(&gravsoft, ntv2.subgrids.get("TEST").unwrap());
+
+        let c = Coor4D::geo(55.06, 12.03, 0., 0.);
+        let (a, b) = (gravsoft.at(&c, 0.).unwrap(), ntv2.at(&c, 0.).unwrap());
+        assert!((a[0] - b[0]).abs() < 1e-10 && (a[1] - b[1]).abs() < 1e-10);
+        // Unknown accuracies are written as -1, and read back as NaN
+        assert!(ntv2.accuracy(&c, 0.).is_none());
+
+        // Only 2 band grids can be written as NTv2
+        let geoid = BaseGrid::gravsoft(&std::fs::read("geodesy/geoid/test.geoid")?)?;
+        assert!(geoid.to_ntv2("GEOID").is_err());
+        assert!(gravsoft.to_ntv2("TOO_LONG_NAME").is_err());
+
+        // ...and only with all values in memory
+        let header = [
+            gravsoft.lat_n,
+            gravsoft.lat_s,
+            gravsoft.lon_w,
+            gravsoft.lon_e,
+        ];
+        let header = [&header[..], &[gravsoft.dlat, gravsoft.dlon, 2.]].concat();
+        let empty = BaseGrid::plain(&header, None, Some(1))?;
+        assert!(empty.to_ntv2("EMPTY").is_err());
+
+        // Accuracy grids must match the shift grids
+        let other = BaseGrid::gravsoft(&std::fs::read("geodesy/datum/test_subset.datum")?)?;
+        let subgrid = ("TEST", "NONE", &gravsoft, Some(&other));
+        assert!(matches!(write_file(&[subgrid]), Err(Error::Invalid(_))));
+        let subgrid = ("TEST", "NONE", &gravsoft, Some(&gravsoft));
+        assert!(write_file(&[subgrid]).is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+//! Albers Equal Area Conic: EPSG coordinate operation method 9822, implemented
+//! following [Snyder (1987)](crate::Bibliography::Sny87), pp. 98-103, but using the
+//! Fourier series by [Karney (2022)](crate::Bibliography::Kar22) for the
+//! conversion from authalic to geographic latitude.
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+const EPS10: f64 = 1e-10;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let e = ellps.eccentricity();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(n) = op.params.real("n") else { return 0 };
+    let Ok(c) = op.params.real("c") else { return 0 };
+    let Ok(q0) = op.params.real("q0") else {
+        return 0;
+    };
+    let root0 = (c - n * q0).max(0.).sqrt();
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+
+        // Snyder (1987) eq. 14-3
+        let q = ancillary::qs(lat.sin(), e);
+        let arg = c - n * q;
+        if arg < -EPS10 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let root = arg.max(0.).sqrt();
+        let rho = a * root / n;
+
+        // Snyder (1987) eq. 14-2, but computing 𝜌₀ - 𝜌 and 1 - cos 𝜃 directly,
+        // rather than by cancellation of large, almost equal, quantities
+        let drho = if root0 + root > 0. {
+            a * (q - q0) / (root0 + root)
+        } else {
+            0.
+        };
+        let theta = n * angular::normalize_symmetric(lon - lon_0);
+        let x = rho * theta.sin() + x_0;
+        let y = drho + 2. * rho * (theta / 2.).sin().powi(2) + y_0;
+        operands.set_xy(i, x, y);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(n) = op.params.real("n") else { return 0 };
+    let Ok(c) = op.params.real("c") else { return 0 };
+    let Ok(qp) = op.params.real("qp") else {
+        return 0;
+    };
+    let Ok(rho0) = op.params.real("rho0") else {
+        return 0;
+    };
+    let Ok(authalic) = op.params.fourier_coefficients("authalic") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let mut x = x - x_0;
+        let mut y = rho0 - (y - y_0);
+
+        // Standard parallels on the southern hemisphere?
+        if n < 0. {
+            x = -x;
+            y = -y;
+        }
+        let rho = x.hypot(y);
+
+        // Snyder (1987) eq. 14-19
+        let q = (c - (rho * n / a).powi(2)) / n;
+
+        // The authalic latitude, 𝜉. Allow for a bit of numerical noise near the poles
+        let sin_xi = q / qp;
+        if sin_xi.abs() > 1. + EPS10 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let xi = sin_xi.clamp(-1., 1.).asin();
+        let lat = ellps.latitude_authalic_to_geographic(xi, &authalic);
+
+        let lon = angular::normalize_symmetric(x.atan2(y) / n + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 8] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lat_1", default: Some(0_f64) },
+    OpParameter::Real { key: "lat_2", default: Some(f64::NAN) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    let phi1 = params.lat(1).to_radians();
+    let mut phi2 = params.lat(2).to_radians();
+    if phi2.is_nan() {
+        phi2 = phi1;
+    }
+
+    if (phi1 + phi2).abs() < EPS10 {
+        return Err(Error::General(
+            "Aea: Invalid value for lat_1 and lat_2: |lat_1 + lat_2| should be > 0",
+        ));
+    }
+    if phi1.abs() > FRAC_PI_2 || phi2.abs() > FRAC_PI_2 {
+        return Err(Error::General(
+            "Aea: Invalid value for lat_1 or lat_2: |lat| should be <= 90°",
+        ));
+    }
+
+    let ellps = params.ellps(0);
+    let e = ellps.eccentricity();
+    let es = ellps.eccentricity_squared();
+
+    // Snyder (1987) eqs. 14-15 and 12-15
+    let sc1 = phi1.sin_cos();
+    let m1 = ancillary::pj_msfn(sc1, es);
+    let q1 = ancillary::qs(sc1.0, e);
+
+    // Snyder (1987) eq. 14-14 (secant case) or 14-14a (tangent case)
+    let n = if (phi1 - phi2).abs() >= EPS10 {
+        let sc2 = phi2.sin_cos();
+        let m2 = ancillary::pj_msfn(sc2, es);
+        let q2 = ancillary::qs(sc2.0, e);
+        if (q1 - q2).abs() < EPS10 {
+            return Err(Error::General("Aea: Invalid value for eccentricity"));
+        }
+        (m1 * m1 - m2 * m2) / (q2 - q1)
+    } else {
+        sc1.0
+    };
+
+    // Snyder (1987) eqs. 14-13 and 14-12a
+    let c = m1 * m1 + n * q1;
+    let q0 = ancillary::qs(lat_0.sin(), e);
+    let rho0 = ellps.semimajor_axis() * (c - n * q0).max(0.).sqrt() / n;
+
+    params.real.insert("n", n);
+    params.real.insert("c", c);
+    params.real.insert("qp", ancillary::qs(1.0, e));
+    params.real.insert("q0", q0);
+    params.real.insert("rho0", rho0);
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lat_1", phi1);
+    params.real.insert("lat_2", phi2);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let authalic = ellps.coefficients_for_authalic_latitude_computations();
+    params.fourier_coefficients.insert("authalic", authalic);
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snyder() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // Worked example from Snyder (1987), pp. 292-293
+        let op = ctx.op("aea lat_1=29.5 lat_2=45.5 lat_0=23 lon_0=-96 ellps=clrk66")?;
+
+        let geo = [Coor4D::geo(35., -75., 0., 0.)];
+        let projected = [Coor4D::raw(1_885_472.7, 1_535_925.0, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert!(operands[0].hypot2(&projected[0]) < 0.1);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert!(operands[0].hypot2(&geo[0]) < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn two_standard_parallels() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("aea lat_1=29.5 lat_2=45.5 lat_0=23 lon_0=-96")?;
+
+        // Validation values computed from the closed form expressions in
+        // Snyder (1987), using 40 digit arithmetic (Python's mpmath)
+        let geo = [
+            Coor4D::geo(30., -90., 0., 0.),
+            Coor4D::geo(40., -100., 0., 0.),
+            Coor4D::geo(28., -80., 0., 0.),
+            Coor4D::geo(45., -110., 0., 0.),
+        ];
+        let projected = [
+            Coor4D::raw(577_912.451_968_513_3, 787_624.280_525_076_6, 0., 0.),
+            Coor4D::raw(-338_390.587_550_867_6, 1_894_100.140_042_562_7, 0., 0.),
+            Coor4D::raw(1_571_950.779_725_703_4, 680_603.637_535_323_6, 0., 0.),
+            Coor4D::raw(-1_098_421.384_278_670_4, 2_526_775.379_573_431_3, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&projected[i]) < 3e-9);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn one_standard_parallel() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("aea lat_1=52 lat_0=52 lon_0=10")?;
+
+        // Validation values computed from the closed form expressions in
+        // Snyder (1987), using 40 digit arithmetic (Python's mpmath)
+        let geo = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(48., 5., 0., 0.),
+            Coor4D::geo(60., 20., 0., 0.),
+        ];
+        let projected = [
+            Coor4D::raw(128_160.136_290_743_8, 335_489.129_189_489_4, 0., 0.),
+            Coor4D::raw(-373_667.655_013_568_5, -431_724.758_173_161_8, 0., 0.),
+            Coor4D::raw(562_957.511_549_193_4, 926_148.073_224_952_4, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&projected[i]) < 3e-9);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn conus_albers() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:5070, NAD83 / Conus Albers
+        let op = ctx.op("aea lat_1=29.5 lat_2=45.5 lat_0=23 lon_0=-96 ellps=GRS80")?;
+
+        let geo = [
+            Coor4D::geo(23., -96., 0., 0.),
+            Coor4D::geo(49., -125., 0., 0.),
+            Coor4D::geo(25., -67., 0., 0.),
+            Coor4D::geo(-30., 30., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+
+        // The projection origin maps to the false origin
+        assert!(operands[0].hypot2(&Coor4D::origin()) < 1e-9);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn southern_hemisphere() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // The South African Albers grid, with both standard parallels on the southern hemisphere
+        let op = ctx.op("aea lat_1=-24 lat_2=-33 lat_0=0 lon_0=24")?;
+
+        let geo = [
+            Coor4D::geo(-30., 24., 0., 0.),
+            Coor4D::geo(-22., 32., 0., 0.),
+            Coor4D::geo(-34., 17., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert!(operands[0][0].abs() < 1e-9);
+        assert!(operands[0][1] < 0.);
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_parameters() {
+        let mut ctx = Minimal::default();
+        assert!(ctx.op("aea lat_1=30 lat_2=-30").is_err());
+        assert!(ctx.op("aea lat_1=91").is_err());
+    }
+}
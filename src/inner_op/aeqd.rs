@@ -0,0 +1,289 @@
+//! Azimuthal Equidistant: The distance and azimuth from the projection center
+//! are given by the geodesic to the point, so the ellipsoidal case is just a
+//! thin wrapper around the [Geodesics](crate::ellps::Geodesics) trait.
+//! Also implements the Guam approximation, EPSG coordinate operation method 9831,
+//! following [IOGP, 2019](crate::Bibliography::Iogp19)
+use crate::authoring::*;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let origin = Coor2D::raw(op.params.lon(0), op.params.lat(0));
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let geodesic = ellps.geodesic_inv(&origin, &Coor2D::raw(lon, lat));
+
+        // No convergence? (near-antipodal points)
+        if geodesic[3] > 990.0 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        let (sin_azi, cos_azi) = geodesic[0].sin_cos();
+        let distance = geodesic[2];
+        operands.set_xy(i, x_0 + distance * sin_azi, y_0 + distance * cos_azi);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let origin = Coor2D::raw(op.params.lon(0), op.params.lat(0));
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = (x - x_0, y - y_0);
+        let distance = x.hypot(y);
+        let azimuth = x.atan2(y);
+
+        let destination = ellps.geodesic_fwd(&origin, azimuth, distance);
+
+        // No convergence?
+        if destination[3] > 990.0 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        let lon = angular::normalize_symmetric(destination[0]);
+        operands.set_xy(i, lon, destination[1]);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- F O R W A R D   G U A M -------------------------------------------------------
+
+fn guam_fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(m0) = op.params.real("m0") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let lam = lon - lon_0;
+        let (sin_phi, cos_phi) = lat.sin_cos();
+
+        // ν, the prime vertical radius of curvature
+        let t = a / (1.0 - es * sin_phi * sin_phi).sqrt();
+
+        let x = x_0 + lam * cos_phi * t;
+        let m = ellps.meridian_latitude_to_distance(lat);
+        let y = y_0 + m - m0 + 0.5 * lam * lam * cos_phi * sin_phi * t;
+        operands.set_xy(i, x, y);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E   G U A M -------------------------------------------------------
+
+fn guam_inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let e = ellps.eccentricity();
+    let lon_0 = op.params.lon(0);
+    let lat_0 = op.params.lat(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(m0) = op.params.real("m0") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = (x - x_0, y - y_0);
+        let x2 = 0.5 * x * x / a;
+
+        // Three iterations suffice, as per the IOGP text
+        let mut lat = lat_0;
+        let mut t = 1.0;
+        for _ in 0..3 {
+            let es_sin_phi = e * lat.sin();
+            t = (1.0 - es_sin_phi * es_sin_phi).sqrt();
+            lat = ellps.meridian_distance_to_latitude(m0 + y - x2 * lat.tan() * t);
+        }
+
+        let lon = lon_0 + x * t / (a * lat.cos());
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 7] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Flag { key: "guam" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    if lat_0.is_nan() || lat_0.abs() > std::f64::consts::FRAC_PI_2 {
+        warn!("Aeqd: Bad central latitude!");
+        return Err(Error::BadParam("lat_0".to_string(), def.clone()));
+    }
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = if params.boolean("guam") {
+        let m0 = params.ellps(0).meridian_latitude_to_distance(lat_0);
+        params.real.insert("m0", m0);
+        OpDescriptor::new(def, InnerOp(guam_fwd), Some(InnerOp(guam_inv)))
+    } else {
+        OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)))
+    };
+
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn geodesic_distances() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("aeqd lat_0=55 lon_0=12 x_0=100000 y_0=200000")?;
+
+        let geo = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(49., 2., 0., 0.),
+            Coor4D::geo(-33.9, 18.4, 0., 0.),
+            Coor4D::geo(64.1, -21.9, 0., 0.),
+            Coor4D::geo(90., 0., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+
+        // The projection center maps to the false origin...
+        assert_float_eq!(operands[0][0], 100_000., abs <= 1e-9);
+        assert_float_eq!(operands[0][1], 200_000., abs <= 1e-9);
+
+        // ... and the distance from the false origin is the geodesic distance
+        let ellps = Ellipsoid::named("GRS80")?;
+        let false_origin = Coor2D::raw(100_000., 200_000.);
+        for i in 1..operands.len() {
+            let projected = Coor2D::raw(operands[i][0], operands[i][1]);
+            let d = ellps.distance(&geo[0], &geo[i]);
+            assert_float_eq!(projected.hypot2(&false_origin), d, abs <= 1e-6);
+        }
+
+        // Copenhagen-Paris, cf. the documentation for `Geodesics::distance`
+        let projected = Coor2D::raw(operands[1][0], operands[1][1]);
+        assert_float_eq!(
+            projected.hypot2(&false_origin),
+            956_066.231_959,
+            abs <= 1e-5
+        );
+
+        // The north pole is due north of the center
+        assert_float_eq!(operands[4][0], 100_000., abs <= 1e-6);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i][1], geo[i][1], abs <= 1e-11);
+        }
+        for i in 0..operands.len() - 1 {
+            assert_float_eq!(operands[i][0], geo[i][0], abs <= 1e-11);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn polar() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("aeqd lat_0=-90")?;
+
+        let geo = [
+            Coor4D::geo(-80., 0., 0., 0.),
+            Coor4D::geo(-60., 90., 0., 0.),
+            Coor4D::geo(-75., -135., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+
+        // Meridians are straight lines radiating from the pole
+        assert!(operands[0][0].abs() < 1e-6);
+        assert!(operands[0][1] > 0.);
+        assert!(operands[1][1].abs() < 1e-6);
+        assert!(operands[1][0] > 0.);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-11);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn guam() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:3993, Guam 1963 / Guam SPCS
+        let op = ctx.op(
+            "aeqd guam lat_0=13:28:20.87887 lon_0=144:44:55.50254 x_0=50000 y_0=50000 ellps=clrk66",
+        )?;
+
+        // Test point from IOGP Guidance Note 7-2
+        let geo = [Coor4D::geo(
+            angular::dms_to_dd(13, 20, 20.53846),
+            angular::dms_to_dd(144, 38, 7.19265),
+            0.,
+            0.,
+        )];
+        let projected = [Coor4D::raw(37_712.48, 35_242.00, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], projected[0][0], abs <= 0.01);
+        assert_float_eq!(operands[0][1], projected[0][1], abs <= 0.01);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0][0], geo[0][0], abs <= 1e-9);
+        assert_float_eq!(operands[0][1], geo[0][1], abs <= 1e-9);
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+//! Cassini-Soldner: EPSG coordinate operation method 9806, implemented following
+//! [IOGP, 2019](crate::Bibliography::Iogp19) and
+//! [Snyder (1987)](crate::Bibliography::Sny87), pp. 92-95
+use crate::authoring::*;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(m0) = op.params.real("m0") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let (sin_phi, cos_phi) = lat.sin_cos();
+
+        // The prime vertical radius of curvature, ν
+        let nu = a / (1. - es * sin_phi * sin_phi).sqrt();
+
+        // Snyder (1987) eqs. 8-2, 8-3, 8-4
+        let tan_phi = sin_phi / cos_phi;
+        let t = tan_phi * tan_phi;
+        let c = es * cos_phi * cos_phi / (1. - es);
+        let aa = angular::normalize_symmetric(lon - lon_0) * cos_phi;
+        let a2 = aa * aa;
+
+        // Snyder (1987) eqs. 13-7, 13-8
+        let x = nu * aa * (1. - a2 * t * (1. / 6. - (8. - t + 8. * c) * a2 / 120.));
+        let m = ellps.meridian_latitude_to_distance(lat);
+        let y = m - m0 + nu * tan_phi * a2 * (0.5 + (5. - t + 6. * c) * a2 / 24.);
+
+        operands.set_xy(i, x + x_0, y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(m0) = op.params.real("m0") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = (x - x_0, y - y_0);
+
+        // The footpoint latitude, φ₁
+        let phi1 = ellps.meridian_distance_to_latitude(m0 + y);
+        let (sin_phi1, cos_phi1) = phi1.sin_cos();
+        let tan_phi1 = sin_phi1 / cos_phi1;
+        let t = tan_phi1 * tan_phi1;
+
+        // Radii of curvature in the prime vertical, ν, and in the meridian, ρ
+        let w = 1. - es * sin_phi1 * sin_phi1;
+        let nu = a / w.sqrt();
+        let rho = a * (1. - es) / (w * w.sqrt());
+
+        // Snyder (1987) eqs. 13-9, 13-10
+        let d = x / nu;
+        let d2 = d * d;
+        let lat = phi1 - (nu * tan_phi1 / rho) * d2 * (0.5 - (1. + 3. * t) * d2 / 24.);
+        let lon = d * (1. + t * d2 * (-1. / 3. + (1. + 3. * t) * d2 / 15.)) / cos_phi1;
+
+        operands.set_xy(i, angular::normalize_symmetric(lon + lon_0), lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 6] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    if lat_0.is_nan() || lat_0.abs() > std::f64::consts::FRAC_PI_2 {
+        warn!("Cass: Bad central latitude!");
+        return Err(Error::BadParam("lat_0".to_string(), def.clone()));
+    }
+
+    let m0 = params.ellps(0).meridian_latitude_to_distance(lat_0);
+    params.real.insert("m0", m0);
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn trinidad() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:30200, Trinidad 1903 / Trinidad Grid. The Clarke 1858 ellipsoid
+        // and the false origin are given in Clarke's links (0.66 Clarke's feet)
+        let op = ctx.op(
+            "cass lat_0=10:26:30 lon_0=-61:20 x_0=430000 y_0=325000 ellps=31706587.88,294.2606764",
+        )?;
+
+        // Test point from IOGP Guidance Note 7-2
+        let geo = [Coor4D::geo(10., -62., 0., 0.)];
+        let projected = [Coor4D::raw(66_644.94, 82_536.22, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], projected[0][0], abs <= 0.01);
+        assert_float_eq!(operands[0][1], projected[0][1], abs <= 0.01);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn central_meridian() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("cass lat_0=50 lon_0=10 x_0=100000")?;
+
+        // The central meridian is mapped true to scale
+        let geo = [
+            Coor4D::geo(50., 10., 0., 0.),
+            Coor4D::geo(52., 10., 0., 0.),
+            Coor4D::geo(51., 11.5, 0., 0.),
+            Coor4D::geo(48., 8., 0., 0.),
+        ];
+        let ellps = Ellipsoid::named("GRS80")?;
+        let dm = ellps.meridian_latitude_to_distance(52_f64.to_radians())
+            - ellps.meridian_latitude_to_distance(50_f64.to_radians());
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], 100_000., abs <= 1e-9);
+        assert_float_eq!(operands[0][1], 0., abs <= 1e-9);
+        assert_float_eq!(operands[1][0], 100_000., abs <= 1e-9);
+        assert_float_eq!(operands[1][1], dm, abs <= 1e-6);
+
+        // The series expansions are truncated, so the roundtrip deteriorates
+        // away from the central meridian: 1e-8 rad is 6 cm at 2° off
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-8);
+        }
+        Ok(())
+    }
+}
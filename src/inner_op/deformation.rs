@@ -127,6 +127,8 @@ fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
     let ellps = op.params.ellps(0);
     let raw = op.params.boolean("raw");
     let use_null_grid = op.params.boolean("null_grid");
+    let method = op.params.text("interpolation").unwrap_or_default();
+    let method: Interpolation = method.parse().unwrap_or_default();
 
     // Datum shift
     'points: for i in 0..n {
@@ -135,7 +137,7 @@ fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
         for margin in [0.0, 0.5] {
             for grid in grids.iter() {
                 // Interpolated deformation velocity
-                if let Some(v) = grid.at(&geo, margin) {
+                if let Some(v) = grid.at_with(&geo, margin, method) {
                     // The deformation duration may be given either as a fixed duration or
                     // as the difference between the frame epoch and the observation epoch
                     let d = if dt.is_finite() { dt } else { epoch - geo[3] };
@@ -183,6 +185,8 @@ fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
     let ellps = op.params.ellps(0);
     let raw = op.params.boolean("raw");
     let use_null_grid = op.params.boolean("null_grid");
+    let method = op.params.text("interpolation").unwrap_or_default();
+    let method: Interpolation = method.parse().unwrap_or_default();
 
     // Datum shift
     'points: for i in 0..n {
@@ -191,7 +195,7 @@ fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
         for margin in [0.0, 0.5] {
             for grid in grids.iter() {
                 // Interpolated deformation velocity
-                if let Some(v) = grid.at(&geo, margin) {
+                if let Some(v) = grid.at_with(&geo, margin, method) {
                     // The deformation duration may be given either as a fixed duration or
                     // as the difference between the frame epoch and the observation epoch
                     let d = if dt.is_finite() { dt } else { epoch - geo[3] };
@@ -230,7 +234,7 @@ fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
 
 // Example...
 #[rustfmt::skip]
-pub const GAMUT: [OpParameter; 7] = [
+pub const GAMUT: [OpParameter; 8] = [
     OpParameter::Flag { key: "inv" },
     OpParameter::Flag { key: "raw" },
     OpParameter::Texts { key: "grids",   default: None },
@@ -238,11 +242,13 @@ pub const GAMUT: [OpParameter; 7] = [
     OpParameter::Real { key: "dt",      default: Some(f64::NAN) },
     OpParameter::Real { key: "t_epoch", default: Some(f64::NAN) },
     OpParameter::Text { key: "ellps",   default: Some("GRS80") },
+    OpParameter::Text { key: "interpolation", default: Some("bilinear") },
 ];
 
 pub fn new(parameters: &RawParameters, ctx: &dyn Context) -> Result<Op, Error> {
     let def = &parameters.definition;
     let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    params.text("interpolation")?.parse::<Interpolation>()?;
 
     if params.real("dt")?.is_nan() && params.real("t_epoch")?.is_nan() {
         return Err(Error::MissingParam(
@@ -428,4 +434,20 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn geotiff() -> Result<(), Error> {
+        // The GeoTIFF version of test.deformation
+        let mut ctx = Plain::default();
+        let op = ctx.op("deformation dt=1000 grids=test_velocity.tif interpolation=bicubic")?;
+        let ellps = Ellipsoid::default();
+        let cph = ellps.cartesian(&Coor4D::geo(55., 12., 0., 0.));
+        let expected_length_of_correction = (55f64 * 55. + 12. * 12.).sqrt();
+
+        let mut data = [cph];
+        ctx.apply(op, Fwd, &mut data)?;
+        let diff = data[0] - cph;
+        assert!((diff.dot(diff).sqrt() - expected_length_of_correction).abs() < 1e-6);
+        Ok(())
+    }
 }
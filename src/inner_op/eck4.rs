@@ -0,0 +1,178 @@
+//! Eckert IV: An equal area pseudocylindrical world projection, with the poles
+//! represented by lines half the length of the equator. The projection is
+//! spherical: the semimajor axis of the ellipsoid is used as radius.
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+// Snyder (1987), p. 256: C_X = 2/√(π(4+π)), C_Y = 2√(π/(4+π)), and C_P = 2 + π/2
+const C_X: f64 = 0.422_238_200_315_771_2;
+const C_Y: f64 = 1.326_500_428_177_002_3;
+const C_P: f64 = 3.570_796_326_794_896_6;
+
+const EPS: f64 = 1e-7;
+const MAX_ITERATIONS: usize = 6;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let lam = angular::normalize_symmetric(lon - lon_0);
+
+        // Solve θ + sin θ cos θ + 2 sin θ = (2 + π/2) sin φ for θ, by Newton-Raphson,
+        // starting from a polynomial approximation
+        let p = C_P * lat.sin();
+        let v = lat * lat;
+        let mut theta = lat * (0.895_168 + v * (0.021_884_9 + v * 0.008_268_09));
+        let mut converged = false;
+        for _ in 0..MAX_ITERATIONS {
+            let (s, c) = theta.sin_cos();
+            let dt = (theta + s * (c + 2.) - p) / (1. + c * (c + 2.) - s * s);
+            theta -= dt;
+            if dt.abs() < EPS {
+                converged = true;
+                break;
+            }
+        }
+
+        let (x, y) = if converged {
+            (C_X * lam * (1. + theta.cos()), C_Y * theta.sin())
+        } else {
+            (C_X * lam, C_Y.copysign(theta))
+        };
+        operands.set_xy(i, a * x + x_0, a * y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = ((x - x_0) / a, (y - y_0) / a);
+
+        let sin_theta = y / C_Y;
+        if sin_theta.abs() > 1. {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let theta = sin_theta.asin();
+        let (s, c) = theta.sin_cos();
+
+        let lat = ((theta + s * (c + 2.)) / C_P).clamp(-1., 1.).asin();
+        let lam = x / (C_X * (1. + c));
+        if lam.abs() > std::f64::consts::PI || lat.abs() > FRAC_PI_2 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        let lon = angular::normalize_symmetric(lam + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 5] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn eck4() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("eck4 ellps=6400000,0")?;
+
+        // Validation values from PROJ builtins.gie, using a sphere with R = 6400 km
+        let geo = [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ];
+
+        let projected = [
+            Coor4D::raw(188_646.389_356_416, 132_268.540_174_065, 0., 0.),
+            Coor4D::raw(188_646.389_356_416, -132_268.540_174_065, 0., 0.),
+            Coor4D::raw(-188_646.389_356_416, 132_268.540_174_065, 0., 0.),
+            Coor4D::raw(-188_646.389_356_416, -132_268.540_174_065, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("eck4 lon_0=150")?;
+
+        let geo = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(-37.5, 150., 0., 0.),
+            Coor4D::geo(89.9, -100., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-10);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+//! Equidistant Cylindrical, and its special case, Plate Carrée (`lat_ts=0`).
+//! Like PROJ, we use the spherical formulae with the semimajor axis as radius,
+//! corresponding to EPSG coordinate operation method 1029
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(rc) = op.params.real("rc") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let x = a * rc * angular::normalize_symmetric(lon - lon_0);
+        let y = a * (lat - lat_0);
+        operands.set_xy(i, x + x_0, y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(rc) = op.params.real("rc") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let lon = (x - x_0) / (a * rc) + lon_0;
+        let lat = (y - y_0) / a + lat_0;
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 7] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps",  default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0",  default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0",  default: Some(0_f64) },
+    OpParameter::Real { key: "lat_ts", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",    default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",    default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_ts = params.real("lat_ts")?.to_radians();
+    let rc = lat_ts.cos();
+    if lat_ts.is_nan() || lat_ts.abs() >= FRAC_PI_2 || rc <= 0. {
+        return Err(Error::General(
+            "Eqc: Invalid value for lat_ts: |lat_ts| should be < 90°",
+        ));
+    }
+
+    params.real.insert("rc", rc);
+    params.real.insert("lat_ts", lat_ts);
+    params.real.insert("lat_0", params.lat(0).to_radians());
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn plate_carree() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:4087, WGS 84 / World Equidistant Cylindrical
+        let op = ctx.op("eqc ellps=WGS84")?;
+
+        // x = a·Δλ, y = a·φ
+        let geo = [Coor4D::geo(55., 10., 0., 0.)];
+        let projected = [Coor4D::raw(1_113_194.91, 6_122_571.99, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, projected[0].0, abs_all <= 0.01);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn lat_ts() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // The PROJ string form should work directly after `parse_proj`
+        let definition = parse_proj("+proj=eqc +lat_ts=60 +lat_0=10 +lon_0=-20 +x_0=100 +y_0=200")?;
+        let op = ctx.op(&definition)?;
+
+        let geo = [
+            Coor4D::geo(10., -20., 0., 0.),
+            Coor4D::geo(70., 150., 0., 0.),
+            Coor4D::geo(-80., 100., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+
+        // Scale along the parallels is cos(lat_ts) = 1/2
+        let a = Ellipsoid::named("GRS80")?.semimajor_axis();
+        assert_float_eq!(operands[0][0], 100., abs <= 1e-9);
+        assert_float_eq!(operands[0][1], 200., abs <= 1e-9);
+        assert_float_eq!(
+            operands[1][0],
+            100. + 0.5 * a * 170_f64.to_radians(),
+            abs <= 1e-6
+        );
+        assert_float_eq!(operands[1][1], 200. + a * 60_f64.to_radians(), abs <= 1e-6);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+
+        assert!(ctx.op("eqc lat_ts=90").is_err());
+        Ok(())
+    }
+}
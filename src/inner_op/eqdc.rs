@@ -0,0 +1,317 @@
+//! Equidistant Conic, implemented following
+//! [Snyder (1987)](crate::Bibliography::Sny87), pp. 111-115, but using the
+//! Fourier series by [Karney (2022)](crate::Bibliography::Kar22) for the
+//! meridian distance computations.
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+const EPS10: f64 = 1e-10;
+
+// Distance from the equator along the meridian, from the Fourier series for
+// the rectifying latitude, which is accurate to well below a nanometre
+fn meridian_distance(ellps: &Ellipsoid, latitude: f64, rectifying: &FourierCoefficients) -> f64 {
+    ellps.semimajor_axis() * ellps.latitude_geographic_to_rectifying(latitude, rectifying)
+}
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(n) = op.params.real("n") else { return 0 };
+    let Ok(g) = op.params.real("g") else { return 0 };
+    let Ok(ml0) = op.params.real("ml0") else {
+        return 0;
+    };
+    let Ok(rectifying) = op.params.fourier_coefficients("rectifying") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+
+        // Snyder (1987) eq. 16-5
+        let ml = meridian_distance(&ellps, lat, &rectifying);
+        let rho = g - ml;
+        let theta = n * angular::normalize_symmetric(lon - lon_0);
+
+        // Snyder (1987) eq. 14-2, but computing 𝜌₀ - 𝜌 and 1 - cos 𝜃 directly,
+        // rather than by cancellation of large, almost equal, quantities
+        let x = rho * theta.sin() + x_0;
+        let y = (ml - ml0) + 2. * rho * (theta / 2.).sin().powi(2) + y_0;
+        operands.set_xy(i, x, y);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(n) = op.params.real("n") else { return 0 };
+    let Ok(g) = op.params.real("g") else { return 0 };
+    let Ok(rho0) = op.params.real("rho0") else {
+        return 0;
+    };
+    let Ok(rectifying) = op.params.fourier_coefficients("rectifying") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let mut x = x - x_0;
+        let mut y = rho0 - (y - y_0);
+
+        // Standard parallels on the southern hemisphere?
+        let mut rho = x.hypot(y);
+        if n < 0. {
+            rho = -rho;
+            x = -x;
+            y = -y;
+        }
+
+        // Snyder (1987) eq. 16-11
+        let ml = (g - rho) / ellps.semimajor_axis();
+        let lat = ellps.latitude_rectifying_to_geographic(ml, &rectifying);
+        if lat.abs() > FRAC_PI_2 + EPS10 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        let lon = angular::normalize_symmetric(x.atan2(y) / n + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 8] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lat_1", default: Some(0_f64) },
+    OpParameter::Real { key: "lat_2", default: Some(f64::NAN) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    let phi1 = params.lat(1).to_radians();
+    let mut phi2 = params.lat(2).to_radians();
+    if phi2.is_nan() {
+        phi2 = phi1;
+    }
+
+    if (phi1 + phi2).abs() < EPS10 {
+        return Err(Error::General(
+            "Eqdc: Invalid value for lat_1 and lat_2: |lat_1 + lat_2| should be > 0",
+        ));
+    }
+    if phi1.abs() >= FRAC_PI_2 || phi2.abs() >= FRAC_PI_2 {
+        return Err(Error::General(
+            "Eqdc: Invalid value for lat_1 or lat_2: |lat| should be < 90°",
+        ));
+    }
+
+    let ellps = params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+
+    // Snyder (1987) eqs. 14-15 and 3-21 (in metric units, rather than in units of a)
+    let sc1 = phi1.sin_cos();
+    let m1 = a * ancillary::pj_msfn(sc1, es);
+    let rectifying = ellps.coefficients_for_rectifying_latitude_computations();
+    let ml1 = meridian_distance(&ellps, phi1, &rectifying);
+
+    // Snyder (1987) eq. 16-3 (secant case), or the tangent case limit
+    let n = if (phi1 - phi2).abs() >= EPS10 {
+        let m2 = a * ancillary::pj_msfn(phi2.sin_cos(), es);
+        let ml2 = meridian_distance(&ellps, phi2, &rectifying);
+        (m1 - m2) / (ml2 - ml1)
+    } else {
+        sc1.0
+    };
+
+    // Snyder (1987) eqs. 16-4 and 16-6
+    let g = m1 / n + ml1;
+    let ml0 = meridian_distance(&ellps, lat_0, &rectifying);
+    let rho0 = g - ml0;
+
+    params.real.insert("n", n);
+    params.real.insert("g", g);
+    params.real.insert("ml0", ml0);
+    params.real.insert("rho0", rho0);
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lat_1", phi1);
+    params.real.insert("lat_2", phi2);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+    params.fourier_coefficients.insert("rectifying", rectifying);
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snyder() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // Worked example from Snyder (1987), pp. 294-295
+        let op = ctx.op("eqdc lat_1=29.5 lat_2=45.5 lat_0=23 lon_0=-96 ellps=clrk66")?;
+
+        let geo = [Coor4D::geo(35., -75., 0., 0.)];
+        let projected = [Coor4D::raw(1_885_051.9, 1_540_507.6, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert!(operands[0].hypot2(&projected[0]) < 0.1);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert!(operands[0].hypot2(&geo[0]) < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn two_standard_parallels() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("eqdc lat_1=29.5 lat_2=45.5 lat_0=23 lon_0=-96")?;
+
+        // Validation values computed from the closed form expressions in
+        // Snyder (1987), using 40 digit arithmetic (Python's mpmath),
+        // and the meridian distances by numerical quadrature
+        let geo = [
+            Coor4D::geo(30., -90., 0., 0.),
+            Coor4D::geo(40., -100., 0., 0.),
+            Coor4D::geo(28., -80., 0., 0.),
+            Coor4D::geo(45., -110., 0., 0.),
+        ];
+        let projected = [
+            Coor4D::raw(577_886.513_408_223_7, 793_943.653_723_184, 0., 0.),
+            Coor4D::raw(-338_415.369_854_675_8, 1_892_161.019_787_669_2, 0., 0.),
+            Coor4D::raw(1_572_083.440_368_650_5, 687_426.706_054_886_9, 0., 0.),
+            Coor4D::raw(-1_098_417.071_018_854_2, 2_521_991.740_192_119_5, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&projected[i]) < 3e-9);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn one_standard_parallel() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("eqdc lat_1=52 lat_0=52 lon_0=10")?;
+
+        // Validation values computed from the closed form expressions in
+        // Snyder (1987), using 40 digit arithmetic (Python's mpmath),
+        // and the meridian distances by numerical quadrature
+        let geo = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(48., 5., 0., 0.),
+            Coor4D::geo(60., 20., 0., 0.),
+        ];
+        let projected = [
+            Coor4D::raw(128_155.725_300_999_5, 335_649.448_925_488_7, 0., 0.),
+            Coor4D::raw(-373_690.889_927_069_7, -432_062.104_674_915_3, 0., 0.),
+            Coor4D::raw(562_497.513_678_574, 929_471.574_520_391_4, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&projected[i]) < 3e-9);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn equidistant_meridians() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("eqdc lat_1=40 lat_2=60 lat_0=50 lon_0=10 x_0=500000")?;
+
+        // Distances along the central meridian are true to scale
+        let geo = [
+            Coor4D::geo(50., 10., 0., 0.),
+            Coor4D::geo(65., 10., 0., 0.),
+            Coor4D::geo(30., 40., 0., 0.),
+            Coor4D::geo(-10., -60., 0., 0.),
+        ];
+        // The meridian distance from 50°N to 65°N, by numerical quadrature
+        let dm = 1_670_492.075_627_239_3;
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert!((operands[0][0] - 500_000.).abs() < 1e-9);
+        assert!(operands[0][1].abs() < 1e-9);
+        assert!((operands[1][1] - dm).abs() < 1e-9);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn one_standard_parallel_southern_hemisphere() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("eqdc lat_1=-35 lon_0=145")?;
+
+        let geo = [
+            Coor4D::geo(-35., 145., 0., 0.),
+            Coor4D::geo(-20., 130., 0., 0.),
+            Coor4D::geo(-43., 150., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert!(operands[i].hypot2(&geo[i]) < 1e-9);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+//! Equal Earth: An equal area pseudocylindrical world projection, by Šavrič,
+//! Patterson and Jenny (2018). EPSG coordinate operation method 1078.
+//! The ellipsoidal form maps the authalic latitude onto a sphere with
+//! the authalic radius.
+use crate::authoring::*;
+use std::f64::consts::PI;
+
+const A1: f64 = 1.340264;
+const A2: f64 = -0.081106;
+const A3: f64 = 0.000893;
+const A4: f64 = 0.003796;
+const M: f64 = 0.866_025_403_784_438_6; // √3/2
+
+const EPS: f64 = 1e-11;
+const MAX_ITERATIONS: usize = 12;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(r_q) = op.params.real("r_q") else {
+        return 0;
+    };
+    let Ok(authalic) = op.params.fourier_coefficients("authalic") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let lam = angular::normalize_symmetric(lon - lon_0);
+
+        // The parametric latitude, θ, from the authalic latitude, β
+        let beta = ellps.latitude_geographic_to_authalic(lat, &authalic);
+        let theta = (M * beta.sin()).asin();
+        let t2 = theta * theta;
+        let t6 = t2 * t2 * t2;
+
+        let x = r_q * lam * theta.cos() / (M * (A1 + 3. * A2 * t2 + t6 * (7. * A3 + 9. * A4 * t2)));
+        let y = r_q * theta * (A1 + A2 * t2 + t6 * (A3 + A4 * t2));
+        operands.set_xy(i, x + x_0, y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(r_q) = op.params.real("r_q") else {
+        return 0;
+    };
+    let Ok(authalic) = op.params.fourier_coefficients("authalic") else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    'points: for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = ((x - x_0) / r_q, (y - y_0) / r_q);
+
+        // Newton-Raphson for the parametric latitude, θ, starting from θ = y
+        let mut theta = y;
+        for _ in 0..MAX_ITERATIONS {
+            let t2 = theta * theta;
+            let t6 = t2 * t2 * t2;
+            let f = theta * (A1 + A2 * t2 + t6 * (A3 + A4 * t2)) - y;
+            let fder = A1 + 3. * A2 * t2 + t6 * (7. * A3 + 9. * A4 * t2);
+            let dt = f / fder;
+            theta -= dt;
+            if dt.abs() >= EPS {
+                continue;
+            }
+
+            // Converged: Recompute the derivative at the final θ
+            let t2 = theta * theta;
+            let t6 = t2 * t2 * t2;
+            let fder = A1 + 3. * A2 * t2 + t6 * (7. * A3 + 9. * A4 * t2);
+
+            let sin_beta = theta.sin() / M;
+            let lam = M * x * fder / theta.cos();
+            if sin_beta.abs() > 1. + 1e-12 || lam.abs() > PI * (1. + 1e-10) {
+                break;
+            }
+
+            let beta = sin_beta.clamp(-1., 1.).asin();
+            let lat = ellps.latitude_authalic_to_geographic(beta, &authalic);
+            let lon = angular::normalize_symmetric(lam + lon_0);
+            operands.set_xy(i, lon, lat);
+            successes += 1;
+            continue 'points;
+        }
+
+        operands.set_xy(i, f64::NAN, f64::NAN);
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 5] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    // The authalic radius, R_q = a·√(q_p/2)
+    let ellps = params.ellps(0);
+    let qp = ancillary::qs(1.0, ellps.eccentricity());
+    let r_q = ellps.semimajor_axis() * (qp / 2.).sqrt();
+
+    params.real.insert("r_q", r_q);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+    let authalic = ellps.coefficients_for_authalic_latitude_computations();
+    params.fourier_coefficients.insert("authalic", authalic);
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn eqearth() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:8857, WGS 84 / Equal Earth Greenwich
+        let op = ctx.op("eqearth ellps=WGS84")?;
+
+        // Validation values from PROJ builtins.gie
+        let geo = [Coor4D::geo(90., -180., 0., 0.)];
+        let projected = [Coor4D::raw(-10_216_474.79, 8_392_927.6, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, projected[0].0, abs_all <= 0.01);
+
+        // On the antimeridian, the inverse may return +180 rather than -180
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0][0].abs(), geo[0][0].abs(), abs <= 1e-12);
+        assert_float_eq!(operands[0][1], geo[0][1], abs <= 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("eqearth lon_0=150")?;
+
+        let geo = [
+            Coor4D::geo(0., 150., 0., 0.),
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(-37.5, 151., 0., 0.),
+            Coor4D::geo(89.9, -100., 0., 0.),
+            Coor4D::geo(-90., -30., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, Coor4D::origin().0, abs_all <= 1e-9);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() - 1 {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-11);
+        }
+
+        // At the pole, only the latitude is well defined
+        assert_float_eq!(operands[4][1], geo[4][1], abs <= 1e-11);
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+//! Geostationary satellite view, following the PROJ implementation. The
+//! projection models the view from a satellite at height `h` above the
+//! equator, scanning along the `sweep` axis (`y` for Meteosat, `x` for GOES).
+//! Points invisible from the satellite are returned as NaN.
+use crate::authoring::*;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let flip_axis = op.params.boolean("flip_axis");
+    let Ok(radius_g_1) = op.params.real("radius_g_1") else {
+        return 0;
+    };
+    let Ok(radius_p) = op.params.real("radius_p") else {
+        return 0;
+    };
+    let radius_g = 1. + radius_g_1;
+    let radius_p2 = radius_p * radius_p;
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let lam = lon - lon_0;
+
+        // The geocentric latitude, and the distance to the point, in units of a
+        let phi = (radius_p2 * lat.tan()).atan();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let r = radius_p / (radius_p * cos_phi).hypot(sin_phi);
+
+        // The vector from the center of the earth to the point
+        let vx = r * lam.cos() * cos_phi;
+        let vy = r * lam.sin() * cos_phi;
+        let vz = r * sin_phi;
+
+        // Visible from the satellite?
+        if (radius_g - vx) * vx - vy * vy - vz * vz / radius_p2 < 0. {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        // The view angles, as seen from the satellite
+        let tmp = radius_g - vx;
+        let (x, y) = if flip_axis {
+            (
+                radius_g_1 * (vy / vz.hypot(tmp)).atan(),
+                radius_g_1 * (vz / tmp).atan(),
+            )
+        } else {
+            (
+                radius_g_1 * (vy / tmp).atan(),
+                radius_g_1 * (vz / vy.hypot(tmp)).atan(),
+            )
+        };
+
+        operands.set_xy(i, a * x + x_0, a * y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let flip_axis = op.params.boolean("flip_axis");
+    let Ok(radius_g_1) = op.params.real("radius_g_1") else {
+        return 0;
+    };
+    let Ok(radius_p) = op.params.real("radius_p") else {
+        return 0;
+    };
+    let radius_g = 1. + radius_g_1;
+    let radius_p2 = radius_p * radius_p;
+    let c = radius_g * radius_g - 1.;
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = ((x - x_0) / a, (y - y_0) / a);
+
+        // The direction of the line of sight from the satellite
+        let vx = -1.;
+        let (vy, vz) = if flip_axis {
+            let vz = (y / radius_g_1).tan();
+            ((x / radius_g_1).tan() * 1_f64.hypot(vz), vz)
+        } else {
+            let vy = (x / radius_g_1).tan();
+            (vy, (y / radius_g_1).tan() * 1_f64.hypot(vy))
+        };
+
+        // Intersect the line of sight with the ellipsoid
+        let aa = vy * vy + (vz / radius_p).powi(2) + vx * vx;
+        let bb = 2. * radius_g * vx;
+        let det = bb * bb - 4. * aa * c;
+        if det < 0. {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let k = (-bb - det.sqrt()) / (2. * aa);
+        let (vx, vy, vz) = (radius_g + k * vx, k * vy, k * vz);
+
+        // And convert the intersection point to geographical coordinates
+        let lam = vy.atan2(vx);
+        let phi = (vz * lam.cos() / vx).atan();
+        let lat = (phi.tan() / radius_p2).atan();
+
+        let lon = angular::normalize_symmetric(lam + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 7] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    // Height of the satellite above the surface
+    OpParameter::Real { key: "h",     default: None },
+
+    // The sweep angle axis of the viewing instrument: `x` or `y`
+    OpParameter::Text { key: "sweep", default: Some("y") },
+
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    let ellps = params.ellps(0);
+
+    let radius_g_1 = params.real("h")? / ellps.semimajor_axis();
+    if !(radius_g_1 > 0. && radius_g_1 <= 1e10) {
+        warn!("Geos: Invalid height!");
+        return Err(Error::BadParam("h".to_string(), def.clone()));
+    }
+
+    match params.text("sweep")?.as_str() {
+        "x" => {
+            params.boolean.insert("flip_axis");
+        }
+        "y" => (),
+        _ => {
+            warn!("Geos: Sweep axis must be x or y!");
+            return Err(Error::BadParam("sweep".to_string(), def.clone()));
+        }
+    }
+
+    // The polar radius, in units of the semimajor axis
+    let radius_p = (1. - ellps.eccentricity_squared()).sqrt();
+
+    params.real.insert("radius_g_1", radius_g_1);
+    params.real.insert("radius_p", radius_p);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn geos() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("geos h=35785831 sweep=y")?;
+
+        // Validation values from PROJ builtins.gie
+        let geo = [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ];
+
+        let projected = [
+            Coor4D::raw(222_527.070_365_800, 110_551.303_413_329, 0., 0.),
+            Coor4D::raw(222_527.070_365_800, -110_551.303_413_329, 0., 0.),
+            Coor4D::raw(-222_527.070_365_800, 110_551.303_413_329, 0., 0.),
+            Coor4D::raw(-222_527.070_365_800, -110_551.303_413_329, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sweep() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // GOES-16: sweep along the x axis
+        let op = ctx.op("geos h=35786023 lon_0=-75 sweep=x ellps=6378137,298.257222096")?;
+        let geo = [
+            Coor4D::geo(40., -100., 0., 0.),
+            Coor4D::geo(-20., -45., 0., 0.),
+            Coor4D::geo(0., 80., 0., 0.),
+        ];
+
+        // The last point is on the far side of the earth
+        let mut operands = geo;
+        assert_eq!(ctx.apply(op, Fwd, &mut operands)?, 2);
+        assert!(operands[2][0].is_nan() && operands[2][1].is_nan());
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..2 {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+
+        // Outside of the disc
+        let mut operands = [Coor4D::raw(6e6, 6e6, 0., 0.)];
+        assert_eq!(ctx.apply(op, Inv, &mut operands)?, 0);
+
+        assert!(ctx.op("geos").is_err());
+        assert!(ctx.op("geos h=35786023 sweep=z").is_err());
+        Ok(())
+    }
+}
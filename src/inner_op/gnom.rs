@@ -0,0 +1,196 @@
+//! Gnomonic: The azimuthal projection from the center of the sphere, mapping
+//! all great circles to straight lines, following
+//! [Snyder (1987)](crate::Bibliography::Sny87), pp. 164-168. The projection is
+//! spherical: the semimajor axis of the ellipsoid is used as radius. Points
+//! 90° or more from the projection center are returned as NaN.
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+const EPS10: f64 = 1e-10;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let (sin_phi_0, cos_phi_0) = lat_0.sin_cos();
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let (sin_phi, cos_phi) = lat.sin_cos();
+        let (sin_lam, cos_lam) = (lon - lon_0).sin_cos();
+
+        // The cosine of the angular distance from the center, Snyder (1987) eq. 5-3
+        let cos_c = sin_phi_0 * sin_phi + cos_phi_0 * cos_phi * cos_lam;
+        if cos_c <= EPS10 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        // Snyder (1987) eqs. 22-4 and 22-5
+        let x = cos_phi * sin_lam / cos_c;
+        let y = (cos_phi_0 * sin_phi - sin_phi_0 * cos_phi * cos_lam) / cos_c;
+        operands.set_xy(i, a * x + x_0, a * y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let (sin_phi_0, cos_phi_0) = lat_0.sin_cos();
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = ((x - x_0) / a, (y - y_0) / a);
+
+        let rh = x.hypot(y);
+        if rh <= EPS10 {
+            operands.set_xy(i, lon_0, lat_0);
+            successes += 1;
+            continue;
+        }
+
+        // Snyder (1987) eqs. 22-16, 20-14 and 20-15
+        let (sin_c, cos_c) = rh.atan().sin_cos();
+        let lat = (cos_c * sin_phi_0 + y * sin_c * cos_phi_0 / rh).asin();
+        let lam = (x * sin_c).atan2(rh * cos_phi_0 * cos_c - y * sin_phi_0 * sin_c);
+
+        let lon = angular::normalize_symmetric(lam + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 6] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    if lat_0.is_nan() || lat_0.abs() > FRAC_PI_2 {
+        warn!("Gnom: Bad central latitude!");
+        return Err(Error::BadParam("lat_0".to_string(), def.clone()));
+    }
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn gnom() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("gnom ellps=6400000,0")?;
+
+        // Validation values from PROJ builtins.gie, using a sphere with R = 6400 km
+        let geo = [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ];
+
+        let projected = [
+            Coor4D::raw(223_492.924_747_185, 111_780.509_206_593, 0., 0.),
+            Coor4D::raw(223_492.924_747_185, -111_780.509_206_593, 0., 0.),
+            Coor4D::raw(-223_492.924_747_185, 111_780.509_206_593, 0., 0.),
+            Coor4D::raw(-223_492.924_747_185, -111_780.509_206_593, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+
+        // The far hemisphere is invisible
+        let mut operands = [
+            Coor4D::geo(0., 90., 0., 0.),
+            Coor4D::geo(-30., 150., 0., 0.),
+        ];
+        assert_eq!(ctx.apply(op, Fwd, &mut operands)?, 0);
+        assert!(operands[0][0].is_nan() && operands[1][0].is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn great_circles() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("gnom lat_0=60 lon_0=-40 ellps=6400000,0")?;
+        let sphere = Ellipsoid::named("6400000,0")?;
+
+        // Copenhagen to New York, densified along the great circle
+        let route = [
+            Coor4D::geo(55.6, 12.6, 0., 0.),
+            Coor4D::geo(40.6, -73.8, 0., 0.),
+        ];
+        let mut operands = sphere.geodesic_densify(&route, 100_000.);
+        assert!(operands.len() > 60);
+
+        // ... is a straight line in the gnomonic projection
+        let n = operands.len();
+        assert_eq!(ctx.apply(op, Fwd, &mut operands)?, n);
+        let (x0, y0) = operands[0].xy();
+        let (x1, y1) = operands[n - 1].xy();
+        let length = (x1 - x0).hypot(y1 - y0);
+        for p in &operands {
+            let (x, y) = p.xy();
+            let offset = ((x1 - x0) * (y - y0) - (y1 - y0) * (x - x0)) / length;
+            assert!(offset.abs() < 1e-6);
+        }
+
+        let mut roundtrip = operands.clone();
+        ctx.apply(op, Inv, &mut roundtrip)?;
+        ctx.apply(op, Fwd, &mut roundtrip)?;
+        for i in 0..n {
+            assert_float_eq!(roundtrip[i].0, operands[i].0, abs_all <= 1e-6);
+        }
+        Ok(())
+    }
+}
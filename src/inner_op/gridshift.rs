@@ -6,6 +6,7 @@ use crate::authoring::*;
 fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
     let grids = &op.params.grids;
     let use_null_grid = op.params.boolean("null_grid");
+    let method = interpolation(op);
 
     let mut successes = 0_usize;
     let n = operands.len();
@@ -18,7 +19,7 @@ fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
     for i in 0..n {
         let mut coord = operands.get_coord(i);
 
-        if let Some(d) = grids_at(grids, &coord, use_null_grid) {
+        if let Some((d, accuracy)) = lookup(op, &coord, use_null_grid, method) {
             // Geoid
             if grids[0].bands() == 1 {
                 coord[2] -= d[0];
@@ -31,6 +32,7 @@ fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
             // Datum shift
             coord[0] += d[0];
             coord[1] += d[1];
+            write_accuracy(op, &mut coord, accuracy);
             operands.set_coord(i, &coord);
             successes += 1;
 
@@ -49,6 +51,7 @@ fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
 fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
     let grids = &op.params.grids;
     let use_null_grid = op.params.boolean("null_grid");
+    let method = interpolation(op);
 
     let mut successes = 0_usize;
     let n = operands.len();
@@ -60,7 +63,7 @@ fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
 
     'points: for i in 0..n {
         let mut coord = operands.get_coord(i);
-        if let Some(t) = grids_at(grids, &coord, use_null_grid) {
+        if let Some((t, _)) = lookup(op, &coord, use_null_grid, method) {
             // Geoid
             if grids[0].bands() == 1 {
                 coord[2] += t[0];
@@ -72,10 +75,11 @@ fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
             // Inverse case datum shift - iteration needed
             let mut t = coord - t;
             for _ in 0..10 {
-                if let Some(t2) = grids_at(grids, &t, use_null_grid) {
+                if let Some((t2, accuracy)) = lookup(op, &t, use_null_grid, method) {
                     let d = t - coord + t2;
                     t = t - d;
                     if d[0].hypot(d[1]) < 1e-12 {
+                        write_accuracy(op, &mut t, accuracy);
                         operands.set_coord(i, &t);
                         successes += 1;
                         continue 'points;
@@ -97,15 +101,59 @@ fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
 // ----- C O N S T R U C T O R ------------------------------------------------------
 
 #[rustfmt::skip]
-pub const GAMUT: [OpParameter; 3] = [
+pub const GAMUT: [OpParameter; 5] = [
     OpParameter::Flag { key: "inv" },
     OpParameter::Texts { key: "grids", default: None },
     OpParameter::Real { key: "padding", default: Some(0.5) },
+    OpParameter::Text { key: "interpolation", default: Some("bilinear") },
+    OpParameter::Natural { key: "accuracy", default: Some(0) },
 ];
 
+// The grid value at `coord`, and, if requested by the `accuracy` parameter,
+// its accuracy, as found in the same grid
+fn lookup(
+    op: &Op,
+    coord: &Coor4D,
+    use_null_grid: bool,
+    method: Interpolation,
+) -> Option<(Coor4D, Option<Coor4D>)> {
+    let grids = &op.params.grids;
+    if op.params.natural("accuracy").unwrap_or(0) == 0 {
+        return grids_at_with(grids, coord, use_null_grid, method).map(|d| (d, None));
+    }
+    grids_at_with_accuracy(grids, coord, use_null_grid, method)
+}
+
+// If requested, write the horizontal `accuracy` of the grid value, in metres,
+// into the component of `coord` given by the `accuracy` parameter (3 or 4).
+// The accuracy is NaN where unknown
+fn write_accuracy(op: &Op, coord: &mut Coor4D, accuracy: Option<Coor4D>) {
+    let component = op.params.natural("accuracy").unwrap_or(0);
+    if component == 0 {
+        return;
+    }
+    coord[component - 1] = accuracy.map_or(f64::NAN, |a| a[0].hypot(a[1]));
+}
+
+// The interpolation method, validated by the constructor
+fn interpolation(op: &Op) -> Interpolation {
+    let method = op.params.text("interpolation").unwrap_or_default();
+    method.parse().unwrap_or_default()
+}
+
 pub fn new(parameters: &RawParameters, ctx: &dyn Context) -> Result<Op, Error> {
     let def = &parameters.definition;
     let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    params.text("interpolation")?.parse::<Interpolation>()?;
+
+    // Accuracy may be written to the third or fourth coordinate component
+    let accuracy = params.natural("accuracy")?;
+    if ![0, 3, 4].contains(&accuracy) {
+        return Err(Error::BadParam(
+            "accuracy".to_string(),
+            accuracy.to_string(),
+        ));
+    }
 
     for mut grid_name in params.texts("grids")?.clone() {
         let optional = grid_name.starts_with('@');
@@ -188,6 +236,108 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn accuracy() -> Result<(), Error> {
+        // 5458_accuracy.gsb has latitude and longitude accuracies of 0.03 and
+        // 0.04 seconds of arc at all nodes, i.e. 0.928 m and 0.711 m at 55°N,
+        // and a horizontal accuracy of 1.169 m
+        let mut ctx = Plain::default();
+        let cph = Coor4D::geo(55., 12., 0., 0.);
+
+        let op = ctx.op("gridshift grids=5458_accuracy.gsb accuracy=4")?;
+        let mut data = [cph];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][3] - 1.168_848).abs() < 1e-6);
+        assert_eq!(data[0][2], 0.);
+        data[0][3] = 0.;
+        ctx.apply(op, Inv, &mut data)?;
+        assert!((data[0][0] - cph[0]).abs() < 1e-10);
+        assert!((data[0][3] - 1.168_848).abs() < 1e-6);
+
+        let op = ctx.op("gridshift grids=5458_accuracy.gsb accuracy=3")?;
+        let mut data = [cph];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][2] - 1.168_848).abs() < 1e-6);
+
+        // Unknown accuracies are NaN
+        let op = ctx.op("gridshift grids=test.datum accuracy=3")?;
+        let mut data = [cph];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!(data[0][2].is_nan());
+
+        assert!(ctx.op("gridshift grids=test.datum accuracy=2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn geotiff() -> Result<(), Error> {
+        let mut ctx = Plain::default();
+        let op = ctx.op("gridshift grids=test_datum.tif")?;
+        let cph = Coor4D::geo(55., 12., 0., 0.);
+        let mut data = [cph];
+
+        ctx.apply(op, Fwd, &mut data)?;
+        let res = data[0].to_geo();
+        assert!((res[0] - 55.015278).abs() < 1e-6);
+        assert!((res[1] - 12.003333).abs() < 1e-6);
+
+        ctx.apply(op, Inv, &mut data)?;
+        assert!((data[0][0] - cph[0]).abs() < 1e-10);
+        assert!((data[0][1] - cph[1]).abs() < 1e-10);
+
+        // Geoid grid: The undulation at 55N 12E is 55.12 m
+        let op = ctx.op("gridshift grids=test_geoid.tif")?;
+        let mut data = [cph];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][2] + 55.12).abs() < 1e-5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn geoid_formats() -> Result<(), Error> {
+        let mut ctx = Plain::default();
+        let cph = Coor4D::geo(55., 12., 0., 0.);
+
+        // The undulation at 55N 12E is 55.12 m in all the test geoids
+        for grid in ["test.geoid", "test.gtx", "test.byn", "test.isg"] {
+            let op = ctx.op(&format!("gridshift grids={grid}"))?;
+            let mut data = [cph];
+            ctx.apply(op, Fwd, &mut data)?;
+            assert!((data[0][2] + 55.12).abs() < 1e-5);
+            ctx.apply(op, Inv, &mut data)?;
+            assert!(data[0][2].abs() < 1e-5);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn interpolation() -> Result<(), Error> {
+        let mut ctx = Plain::default();
+        let cph = Coor4D::geo(55.25, 12.75, 0., 0.);
+
+        // test.datum is linear in latitude and longitude, so all
+        // interpolation methods should agree
+        let mut results = Vec::new();
+        for method in ["bilinear", "biquadratic", "bicubic"] {
+            let op = ctx.op(&format!(
+                "gridshift grids=test.datum interpolation={method}"
+            ))?;
+            let mut data = [cph];
+            ctx.apply(op, Fwd, &mut data)?;
+            results.push(data[0]);
+            ctx.apply(op, Inv, &mut data)?;
+            assert!(data[0].hypot2(&cph) < 1e-10);
+        }
+        assert!(results[0].hypot2(&results[1]) < 1e-10);
+        assert!(results[0].hypot2(&results[2]) < 1e-10);
+
+        assert!(ctx
+            .op("gridshift grids=test.datum interpolation=nearest")
+            .is_err());
+        Ok(())
+    }
+
     #[test]
     fn multiple_grids() -> Result<(), Error> {
         let mut ctx = Plain::default();
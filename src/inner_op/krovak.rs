@@ -0,0 +1,285 @@
+//! Krovak: The oblique conformal conic projection used for S-JTSK in Czechia
+//! and Slovakia. EPSG coordinate operation methods 9819 (Krovak) and 1041
+//! (Krovak North Orientated), implemented following
+//! [IOGP, 2019](crate::Bibliography::Iogp19).
+//!
+//! The defaults are those of S-JTSK, with the longitude of origin given
+//! relative to Greenwich, rather than Ferro. The ellipsoid, however, follows
+//! the global default, so S-JTSK needs an explicit `ellps=bessel`. The output is
+//! easting, northing (i.e. negative values in the area of use). The `czech`
+//! flag gives the traditional westing, southing (i.e. positive values).
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_4;
+
+// Convergence criterion for the inverse latitude iteration
+const EPS: f64 = 1e-12;
+const MAX_ITERATIONS: usize = 15;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let e = op.params.ellps(0).eccentricity();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let sign = if op.params.boolean("czech") { 1. } else { -1. };
+    let (Ok(b), Ok(t0), Ok(n), Ok(r0), Ok(alpha), Ok(phi_p)) = (
+        op.params.real("b"),
+        op.params.real("t0"),
+        op.params.real("n"),
+        op.params.real("r0"),
+        op.params.real("alpha"),
+        op.params.real("lat_ts"),
+    ) else {
+        return 0;
+    };
+    let (sin_alpha, cos_alpha) = alpha.sin_cos();
+    let tan_p = (FRAC_PI_4 + phi_p / 2.).tan();
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+
+        // Conformal latitude and longitude on the Gaussian sphere
+        let es = e * lat.sin();
+        let u = 2.
+            * ((t0 * (lat / 2. + FRAC_PI_4).tan().powf(b)
+                / ((1. + es) / (1. - es)).powf(e * b / 2.))
+            .atan()
+                - FRAC_PI_4);
+        let v = b * angular::normalize_symmetric(lon_0 - lon);
+
+        // Oblique latitude and longitude, with the pole at the cone axis
+        let (sin_u, cos_u) = u.sin_cos();
+        let t = (cos_alpha * sin_u + sin_alpha * cos_u * v.cos()).asin();
+        let d = (cos_u * v.sin() / t.cos()).asin();
+
+        // Polar coordinates on the cone
+        let theta = n * d;
+        let r = r0 * (tan_p / (t / 2. + FRAC_PI_4).tan()).powf(n);
+
+        // Southing and westing
+        let southing = r * theta.cos();
+        let westing = r * theta.sin();
+
+        operands.set_xy(i, sign * westing + x_0, sign * southing + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let e = op.params.ellps(0).eccentricity();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let sign = if op.params.boolean("czech") { 1. } else { -1. };
+    let (Ok(b), Ok(t0), Ok(n), Ok(r0), Ok(alpha), Ok(phi_p)) = (
+        op.params.real("b"),
+        op.params.real("t0"),
+        op.params.real("n"),
+        op.params.real("r0"),
+        op.params.real("alpha"),
+        op.params.real("lat_ts"),
+    ) else {
+        return 0;
+    };
+    let (sin_alpha, cos_alpha) = alpha.sin_cos();
+    let tan_p = (FRAC_PI_4 + phi_p / 2.).tan();
+
+    let mut successes = 0_usize;
+    'points: for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let westing = sign * (x - x_0);
+        let southing = sign * (y - y_0);
+
+        // From polar coordinates on the cone to the oblique sphere
+        let r = southing.hypot(westing);
+        let theta = westing.atan2(southing);
+        let d = theta / n;
+        let t = 2. * (((r0 / r).powf(1. / n) * tan_p).atan() - FRAC_PI_4);
+
+        // ... to the Gaussian sphere
+        let (sin_t, cos_t) = t.sin_cos();
+        let u = (cos_alpha * sin_t - sin_alpha * cos_t * d.cos()).asin();
+        let v = (cos_t * d.sin() / u.cos()).asin();
+
+        // ... and to the ellipsoid, iteratively
+        let k = (u / 2. + FRAC_PI_4).tan().powf(1. / b) / t0.powf(1. / b);
+        let mut lat = u;
+        for _ in 0..MAX_ITERATIONS {
+            let es = e * lat.sin();
+            let next = 2. * ((k * ((1. + es) / (1. - es)).powf(e / 2.)).atan() - FRAC_PI_4);
+            let done = (next - lat).abs() < EPS;
+            lat = next;
+            if done {
+                let lon = angular::normalize_symmetric(lon_0 - v / b);
+                operands.set_xy(i, lon, lat);
+                successes += 1;
+                continue 'points;
+            }
+        }
+
+        // No convergence
+        operands.set_xy(i, f64::NAN, f64::NAN);
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 10] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Flag { key: "czech" },
+    OpParameter::Text { key: "ellps",  default: Some("GRS80") },
+
+    // Latitude of the projection center, and longitude of origin (Greenwich)
+    OpParameter::Real { key: "lat_0",  default: Some(49.5) },
+    OpParameter::Real { key: "lon_0",  default: Some(24.833333333333333) },
+
+    // Co-latitude of the cone axis, and latitude of the pseudo standard parallel
+    OpParameter::Real { key: "alpha",  default: Some(30.288139752777778) },
+    OpParameter::Real { key: "lat_ts", default: Some(78.5) },
+
+    // Scale factor on the pseudo standard parallel
+    OpParameter::Real { key: "k_0",    default: Some(0.9999) },
+
+    OpParameter::Real { key: "x_0",    default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",    default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let phi_c = params.lat(0).to_radians();
+    let alpha = params.real("alpha")?.to_radians();
+    let phi_p = params.real("lat_ts")?.to_radians();
+    let k_p = params.k(0);
+    if phi_p.sin().abs() < 1e-10 || phi_p.abs() >= std::f64::consts::FRAC_PI_2 {
+        return Err(Error::BadParam("lat_ts".to_string(), def.clone()));
+    }
+
+    let ellps = params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let e = ellps.eccentricity();
+    let es = ellps.eccentricity_squared();
+    let (sin_phi_c, cos_phi_c) = phi_c.sin_cos();
+
+    // Radius of the Gaussian sphere, and the sphere-to-ellipsoid constants
+    let aa = a * (1. - es).sqrt() / (1. - es * sin_phi_c * sin_phi_c);
+    let b = (1. + es * cos_phi_c.powi(4) / (1. - es)).sqrt();
+    let gamma_0 = (sin_phi_c / b).asin();
+    let esc = e * sin_phi_c;
+    let t0 = (FRAC_PI_4 + gamma_0 / 2.).tan() * ((1. + esc) / (1. - esc)).powf(e * b / 2.)
+        / (FRAC_PI_4 + phi_c / 2.).tan().powf(b);
+
+    // The cone constant, and the radius of the pseudo standard parallel
+    let n = phi_p.sin();
+    let r0 = k_p * aa / phi_p.tan();
+
+    params.real.insert("b", b);
+    params.real.insert("t0", t0);
+    params.real.insert("n", n);
+    params.real.insert("r0", r0);
+    params.real.insert("alpha", alpha);
+    params.real.insert("lat_ts", phi_p);
+    params.real.insert("lat_0", phi_c);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    // Test point from IOGP Guidance Note 7-2 (EPSG methods 9819 and 1041),
+    // with the longitude converted from Ferro to Greenwich
+    fn geo() -> [Coor4D; 1] {
+        [Coor4D::geo(
+            angular::dms_to_dd(50, 12, 32.442),
+            angular::dms_to_dd(16, 50, 59.179),
+            0.,
+            0.,
+        )]
+    }
+
+    #[test]
+    fn czech() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("krovak czech ellps=bessel")?;
+
+        // Westing, southing
+        let projected = [Coor4D::raw(568_991.00, 1_050_538.64, 0., 0.)];
+
+        let mut operands = geo();
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], projected[0][0], abs <= 0.01);
+        assert_float_eq!(operands[0][1], projected[0][1], abs <= 0.01);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo()[0].0, abs_all <= 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn east_north() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:5514, S-JTSK / Krovak East North, in the form given by `projinfo`
+        let definition = parse_proj(
+            "+proj=krovak +lat_0=49.5 +lon_0=24.8333333333333 +alpha=30.2881397527778 +k=0.9999 +x_0=0 +y_0=0 +ellps=bessel",
+        )?;
+        let op = ctx.op(&definition)?;
+
+        // Easting, northing
+        let projected = [Coor4D::raw(-568_991.00, -1_050_538.64, 0., 0.)];
+
+        let mut operands = geo();
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], projected[0][0], abs <= 0.01);
+        assert_float_eq!(operands[0][1], projected[0][1], abs <= 0.01);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo()[0].0, abs_all <= 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("krovak ellps=bessel")?;
+
+        let geo = [
+            Coor4D::geo(48.6, 12.1, 0., 0.),
+            Coor4D::geo(51.1, 14.7, 0., 0.),
+            Coor4D::geo(49.6, 18.8, 0., 0.),
+            Coor4D::geo(47.7, 22.6, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+
+        assert!(ctx.op("krovak lat_ts=0").is_err());
+        Ok(())
+    }
+}
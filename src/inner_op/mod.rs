@@ -7,61 +7,102 @@ use crate::authoring::*;
 
 mod adapt;
 mod addone;
+mod aea;
+mod aeqd;
 mod axisswap;
 mod btmerc;
 mod cart;
+mod cass;
 mod curvature;
 mod deflection;
 mod deformation;
+mod eck4;
+mod eqc;
+mod eqdc;
+mod eqearth;
 mod geodesic;
+mod geos;
+mod gnom;
 mod gravity;
 mod gridshift;
 mod helmert;
 mod iso6709;
+mod krovak;
 mod laea;
 mod latitude;
 mod lcc;
 mod merc;
+mod moll;
 mod molodensky;
 mod noop;
+mod nsper;
 mod omerc;
+mod ortho;
 pub(crate) mod pipeline; // Needed by Op for instantiation
 mod pushpop;
+mod robin;
+mod sinu;
 mod somerc;
 mod stack;
+mod stere;
+mod sterea;
 mod tmerc;
 mod unitconvert;
 mod units;
+mod utmups;
+mod vgridshift;
 mod webmerc;
 
 #[rustfmt::skip]
-const BUILTIN_OPERATORS: [(&str, OpConstructor); 35] = [
+const BUILTIN_OPERATORS: [(&str, OpConstructor); 57] = [
     ("adapt",        OpConstructor(adapt::new)),
     ("addone",       OpConstructor(addone::new)),
+    ("aea",          OpConstructor(aea::new)),
+    ("aeqd",         OpConstructor(aeqd::new)),
     ("axisswap",     OpConstructor(axisswap::new)),
     ("btmerc",       OpConstructor(btmerc::new)),
     ("butm",         OpConstructor(btmerc::utm)),
     ("cart",         OpConstructor(cart::new)),
+    ("cass",         OpConstructor(cass::new)),
     ("curvature",    OpConstructor(curvature::new)),
     ("deflection",   OpConstructor(deflection::new)),
     ("deformation",  OpConstructor(deformation::new)),
     ("dm",           OpConstructor(iso6709::dm)),
     ("dms",          OpConstructor(iso6709::dms)),
+    ("eck4",         OpConstructor(eck4::new)),
+    ("eqc",          OpConstructor(eqc::new)),
+    ("eqdc",         OpConstructor(eqdc::new)),
+    ("eqearth",      OpConstructor(eqearth::new)),
     ("geodesic",     OpConstructor(geodesic::new)),
+    ("geos",         OpConstructor(geos::new)),
+    ("gk",           OpConstructor(tmerc::gk)),
+    ("gnom",         OpConstructor(gnom::new)),
     ("gravity",      OpConstructor(gravity::new)),
     ("gridshift",    OpConstructor(gridshift::new)),
     ("helmert",      OpConstructor(helmert::new)),
+    ("krovak",       OpConstructor(krovak::new)),
     ("laea",         OpConstructor(laea::new)),
     ("latitude",     OpConstructor(latitude::new)),
     ("lcc",          OpConstructor(lcc::new)),
     ("merc",         OpConstructor(merc::new)),
     ("webmerc",      OpConstructor(webmerc::new)),
     ("molodensky",   OpConstructor(molodensky::new)),
+    ("moll",         OpConstructor(moll::new)),
+    ("nsper",        OpConstructor(nsper::new)),
     ("omerc",        OpConstructor(omerc::new)),
+    ("ortho",        OpConstructor(ortho::new)),
+    ("robin",        OpConstructor(robin::new)),
+    ("sinu",         OpConstructor(sinu::new)),
     ("somerc",       OpConstructor(somerc::new)),
+    ("stere",        OpConstructor(stere::new)),
+    ("sterea",       OpConstructor(sterea::new)),
     ("tmerc",        OpConstructor(tmerc::new)),
+    ("tpers",        OpConstructor(nsper::tpers)),
     ("unitconvert",  OpConstructor(unitconvert::new)),
+    ("ups",          OpConstructor(stere::ups)),
     ("utm",          OpConstructor(tmerc::utm)),
+    ("utmups",       OpConstructor(utmups::new)),
+    ("vgridshift",   OpConstructor(vgridshift::new)),
 
     // Pipeline handlers
     ("pipeline",     OpConstructor(pipeline::new)),
@@ -0,0 +1,184 @@
+//! Mollweide: An equal area pseudocylindrical world projection. The projection
+//! is spherical: the semimajor axis of the ellipsoid is used as radius.
+use crate::authoring::*;
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2};
+
+const C_X: f64 = 2. * SQRT_2 / PI;
+const C_Y: f64 = SQRT_2;
+
+const EPS: f64 = 1e-7;
+const MAX_ITERATIONS: usize = 30;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+
+        // Solve 2θ + sin 2θ = π sin φ for the auxiliary angle, θ, by Newton-Raphson
+        let k = PI * lat.sin();
+        let mut theta = lat;
+        let mut converged = false;
+        for _ in 0..MAX_ITERATIONS {
+            let dt = (theta + theta.sin() - k) / (1. + theta.cos());
+            theta -= dt;
+            if dt.abs() < EPS {
+                converged = true;
+                break;
+            }
+        }
+
+        // Convergence is slow near the poles, where θ → φ → ±90°
+        let theta = if converged {
+            theta / 2.
+        } else {
+            FRAC_PI_2.copysign(lat)
+        };
+
+        let lam = angular::normalize_symmetric(lon - lon_0);
+        let x = a * C_X * lam * theta.cos();
+        let y = a * C_Y * theta.sin();
+        operands.set_xy(i, x + x_0, y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = ((x - x_0) / a, (y - y_0) / a);
+
+        let sin_theta = y / C_Y;
+        if sin_theta.abs() > 1. {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let theta = sin_theta.asin();
+        let cos_theta = theta.cos();
+
+        // At the poles, all longitudes coincide
+        let lam = if cos_theta.abs() < 1e-15 {
+            0.
+        } else {
+            x / (C_X * cos_theta)
+        };
+        if lam.abs() > PI {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        let lat = ((2. * theta + (2. * theta).sin()) / PI)
+            .clamp(-1., 1.)
+            .asin();
+        let lon = angular::normalize_symmetric(lam + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 5] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn moll() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("moll ellps=6400000,0")?;
+
+        // Validation values from PROJ builtins.gie, using a sphere with R = 6400 km
+        let geo = [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ];
+
+        let projected = [
+            Coor4D::raw(201_113.698_641_813, 124_066.283_433_860, 0., 0.),
+            Coor4D::raw(201_113.698_641_813, -124_066.283_433_860, 0., 0.),
+            Coor4D::raw(-201_113.698_641_813, 124_066.283_433_860, 0., 0.),
+            Coor4D::raw(-201_113.698_641_813, -124_066.283_433_860, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("moll lon_0=-90")?;
+
+        let geo = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(-37.5, 150., 0., 0.),
+            Coor4D::geo(89.9, -100., 0., 0.),
+            Coor4D::geo(-90., -90., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-10);
+        }
+        Ok(())
+    }
+}
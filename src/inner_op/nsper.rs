@@ -0,0 +1,317 @@
+//! Near-sided perspective (`nsper`), and its tilted variant (`tpers`), following
+//! the PROJ implementation, and [Snyder (1987)](crate::Bibliography::Sny87),
+//! pp. 169-181. The projections are spherical: the semimajor axis of the
+//! ellipsoid is used as radius. Points on the far side of the horizon, as seen
+//! from the point of perspective, are returned as NaN.
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+const EPS10: f64 = 1e-10;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let (sin_phi_0, cos_phi_0) = lat_0.sin_cos();
+    let north_polar = op.params.boolean("north_polar");
+    let south_polar = op.params.boolean("south_polar");
+    let tilted = op.params.boolean("tilted");
+    let Ok(pn1) = op.params.real("pn1") else {
+        return 0;
+    };
+    let (Ok(tilt), Ok(azi)) = (op.params.real("tilt"), op.params.real("azi")) else {
+        return 0;
+    };
+    let (sin_omega, cos_omega) = tilt.sin_cos();
+    let (sin_gamma, cos_gamma) = azi.sin_cos();
+
+    // Distance of the point of perspective from the center, in units of the radius
+    let p = 1. + pn1;
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let (sin_phi, cos_phi) = lat.sin_cos();
+        let (sin_lam, cos_lam) = (lon - lon_0).sin_cos();
+
+        // The cosine of the angular distance from the center, Snyder (1987) eq. 5-3
+        let cos_c = if north_polar {
+            sin_phi
+        } else if south_polar {
+            -sin_phi
+        } else {
+            sin_phi_0 * sin_phi + cos_phi_0 * cos_phi * cos_lam
+        };
+
+        // Beyond the horizon?
+        if cos_c < 1. / p {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        // Snyder (1987) eqs. 23-1 to 23-3
+        let k = pn1 / (p - cos_c);
+        let mut x = k * cos_phi * sin_lam;
+        let mut y = if north_polar {
+            -k * cos_phi * cos_lam
+        } else if south_polar {
+            k * cos_phi * cos_lam
+        } else {
+            k * (cos_phi_0 * sin_phi - sin_phi_0 * cos_phi * cos_lam)
+        };
+
+        // Snyder (1987) eqs. 23-4 to 23-6
+        if tilted {
+            let yt = y * cos_gamma + x * sin_gamma;
+            let ba = 1. / (yt * sin_omega / pn1 + cos_omega);
+            x = (x * cos_gamma - y * sin_gamma) * cos_omega * ba;
+            y = yt * ba;
+        }
+
+        operands.set_xy(i, a * x + x_0, a * y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let (sin_phi_0, cos_phi_0) = lat_0.sin_cos();
+    let north_polar = op.params.boolean("north_polar");
+    let south_polar = op.params.boolean("south_polar");
+    let tilted = op.params.boolean("tilted");
+    let Ok(pn1) = op.params.real("pn1") else {
+        return 0;
+    };
+    let (Ok(tilt), Ok(azi)) = (op.params.real("tilt"), op.params.real("azi")) else {
+        return 0;
+    };
+    let (sin_omega, cos_omega) = tilt.sin_cos();
+    let (sin_gamma, cos_gamma) = azi.sin_cos();
+    let p = 1. + pn1;
+    let pfact = (p + 1.) / pn1;
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (mut x, mut y) = ((x - x_0) / a, (y - y_0) / a);
+
+        // Undo the tilt
+        if tilted {
+            let yt = 1. / (pn1 - y * sin_omega);
+            let bm = pn1 * x * yt;
+            let bq = pn1 * y * cos_omega * yt;
+            x = bm * cos_gamma + bq * sin_gamma;
+            y = bq * cos_gamma - bm * sin_gamma;
+        }
+
+        let rh = x.hypot(y);
+        if rh <= EPS10 {
+            operands.set_xy(i, lon_0, lat_0);
+            successes += 1;
+            continue;
+        }
+
+        // Outside of the visible disc?
+        let sin_z = 1. - rh * rh * pfact;
+        if sin_z < 0. {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let sin_z = (p - sin_z.sqrt()) / (pn1 / rh + rh / pn1);
+        let cos_z = (1. - sin_z * sin_z).sqrt();
+
+        let (lat, lam) = if north_polar {
+            (cos_z.asin(), x.atan2(-y))
+        } else if south_polar {
+            (-cos_z.asin(), x.atan2(y))
+        } else {
+            let lat = (cos_z * sin_phi_0 + y * sin_z * cos_phi_0 / rh).asin();
+            let yy = (cos_z - sin_phi_0 * lat.sin()) * rh;
+            (lat, (x * sin_z * cos_phi_0).atan2(yy))
+        };
+
+        let lon = angular::normalize_symmetric(lam + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R S -------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 9] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    // Height of the point of perspective above the surface
+    OpParameter::Real { key: "h",     default: None },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    // For `tpers`: Tilt angle, and azimuth of the tilt
+    OpParameter::Real { key: "tilt",  default: Some(0_f64) },
+    OpParameter::Real { key: "azi",   default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    if lat_0.is_nan() || lat_0.abs() > FRAC_PI_2 {
+        warn!("Nsper: Bad central latitude!");
+        return Err(Error::BadParam("lat_0".to_string(), def.clone()));
+    }
+
+    // The height, normalized by the radius
+    let pn1 = params.real("h")? / params.ellps(0).semimajor_axis();
+    if !(pn1 > 0. && pn1 <= 1e10) {
+        warn!("Nsper: Invalid height!");
+        return Err(Error::BadParam("h".to_string(), def.clone()));
+    }
+
+    if (lat_0.abs() - FRAC_PI_2).abs() < EPS10 {
+        if lat_0 > 0. {
+            params.boolean.insert("north_polar");
+        } else {
+            params.boolean.insert("south_polar");
+        }
+    }
+
+    params.real.insert("pn1", pn1);
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+    params
+        .real
+        .insert("tilt", params.real("tilt")?.to_radians());
+    params.real.insert("azi", params.real("azi")?.to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+/// The tilted perspective: `nsper` with the `tilt` and `azi` parameters enabled
+pub fn tpers(parameters: &RawParameters, ctx: &dyn Context) -> Result<Op, Error> {
+    let mut op = new(parameters, ctx)?;
+    op.params.boolean.insert("tilted");
+    Ok(op)
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    fn geo() -> [Coor4D; 4] {
+        [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ]
+    }
+
+    #[test]
+    fn nsper() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("nsper ellps=6400000,0 h=1000000")?;
+
+        // Validation values from PROJ builtins.gie, using a sphere with R = 6400 km
+        let projected = [
+            Coor4D::raw(222_239.816_114_100, 111_153.763_991_925, 0., 0.),
+            Coor4D::raw(222_239.816_114_100, -111_153.763_991_925, 0., 0.),
+            Coor4D::raw(-222_239.816_114_100, 111_153.763_991_925, 0., 0.),
+            Coor4D::raw(-222_239.816_114_100, -111_153.763_991_925, 0., 0.),
+        ];
+
+        let mut operands = geo();
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo()[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tpers() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("tpers ellps=6400000,0 h=1000000 azi=20")?;
+
+        // Validation values from PROJ builtins.gie, using a sphere with R = 6400 km
+        let projected = [
+            Coor4D::raw(170_820.288_955_531, 180_460.865_555_805, 0., 0.),
+            Coor4D::raw(246_853.941_538_942, -28_439.878_035_775, 0., 0.),
+            Coor4D::raw(-246_853.941_538_942, 28_439.878_035_775, 0., 0.),
+            Coor4D::raw(-170_820.288_955_531, -180_460.865_555_805, 0., 0.),
+        ];
+
+        let mut operands = geo();
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo()[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn far_side() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("tpers lat_0=90 h=3000000 tilt=10 azi=30")?;
+
+        let geo = [
+            Coor4D::geo(70., 30., 0., 0.),
+            Coor4D::geo(0., 30., 0., 0.),
+            Coor4D::geo(-60., 100., 0., 0.),
+            Coor4D::geo(60., -120., 0., 0.),
+        ];
+
+        // Only the first and the last points are above the horizon
+        let mut operands = geo;
+        assert_eq!(ctx.apply(op, Fwd, &mut operands)?, 2);
+        assert!(operands[1][0].is_nan() && operands[1][1].is_nan());
+        assert!(operands[2][0].is_nan() && operands[2][1].is_nan());
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-12);
+        assert_float_eq!(operands[3].0, geo[3].0, abs_all <= 1e-12);
+
+        assert!(ctx.op("nsper").is_err());
+        assert!(ctx.op("nsper h=-1").is_err());
+        Ok(())
+    }
+}
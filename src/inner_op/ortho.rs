@@ -0,0 +1,251 @@
+//! Orthographic: EPSG coordinate operation method 9840, i.e. the ellipsoidal
+//! form, implemented following the PROJ implementation. Points on the far side
+//! of the ellipsoid are not visible from the projection plane, and are returned
+//! as NaN.
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+const EPS10: f64 = 1e-10;
+const MAX_ITERATIONS: usize = 20;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let (sin_phi_0, cos_phi_0) = lat_0.sin_cos();
+    let nu_0 = 1. / (1. - es * sin_phi_0 * sin_phi_0).sqrt();
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let (sin_phi, cos_phi) = lat.sin_cos();
+        let (sin_lam, cos_lam) = (lon - lon_0).sin_cos();
+
+        // Visible from the projection plane? I.e. is the dot product of the
+        // ellipsoidal normals at the center and at the point non-negative?
+        if cos_phi * cos_lam * cos_phi_0 + sin_phi * sin_phi_0 < -EPS10 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        let nu = 1. / (1. - es * sin_phi * sin_phi).sqrt();
+        let x = nu * cos_phi * sin_lam;
+        let y = nu * (sin_phi * cos_phi_0 - cos_phi * sin_phi_0 * cos_lam)
+            + es * (nu_0 * sin_phi_0 - nu * sin_phi) * cos_phi_0;
+        operands.set_xy(i, a * x + x_0, a * y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+    let lat_0 = op.params.lat(0);
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let (sin_phi_0, cos_phi_0) = lat_0.sin_cos();
+    let nu_0 = 1. / (1. - es * sin_phi_0 * sin_phi_0).sqrt();
+    let polar = (lat_0.abs() - FRAC_PI_2).abs() < EPS10;
+
+    let mut successes = 0_usize;
+    'points: for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = ((x - x_0) / a, (y - y_0) / a);
+
+        // The polar aspects have a closed form solution
+        if polar {
+            let rh2 = x * x + y * y;
+            let lat = if rh2 >= 1. - 1e-15 {
+                if rh2 - 1. > EPS10 {
+                    operands.set_xy(i, f64::NAN, f64::NAN);
+                    continue;
+                }
+                0.
+            } else {
+                ((rh2 * (1. - es) / (1. - es * rh2)).sqrt().acos()).copysign(lat_0)
+            };
+            let lon = x.atan2(-y * lat_0.signum()) + lon_0;
+            operands.set_xy(i, angular::normalize_symmetric(lon), lat);
+            successes += 1;
+            continue;
+        }
+
+        // Otherwise, Newton-Raphson starting from the projection center
+        let mut lat = lat_0;
+        let mut lam = 0_f64;
+        for _ in 0..MAX_ITERATIONS {
+            let (sin_phi, cos_phi) = lat.sin_cos();
+            let (sin_lam, cos_lam) = lam.sin_cos();
+            let w = 1. - es * sin_phi * sin_phi;
+            let nu = 1. / w.sqrt();
+            let rho = (1. - es) * nu / w;
+
+            // The forward projection of the current estimate...
+            let xx = nu * cos_phi * sin_lam;
+            let yy = nu * (sin_phi * cos_phi_0 - cos_phi * sin_phi_0 * cos_lam)
+                + es * (nu_0 * sin_phi_0 - nu * sin_phi) * cos_phi_0;
+
+            // ... and its Jacobian
+            let j11 = -rho * sin_phi * sin_lam;
+            let j12 = nu * cos_phi * cos_lam;
+            let j21 = rho * (cos_phi * cos_phi_0 + sin_phi * sin_phi_0 * cos_lam);
+            let j22 = nu * sin_phi_0 * cos_phi * sin_lam;
+            let d = j11 * j22 - j12 * j21;
+
+            let (dx, dy) = (x - xx, y - yy);
+            let dphi = (j22 * dx - j12 * dy) / d;
+            let dlam = (-j21 * dx + j11 * dy) / d;
+            lat = (lat + dphi).clamp(-FRAC_PI_2, FRAC_PI_2);
+            lam += dlam;
+
+            if dphi.abs() < 1e-12 && dlam.abs() < 1e-12 {
+                let lon = angular::normalize_symmetric(lam + lon_0);
+                operands.set_xy(i, lon, lat);
+                successes += 1;
+                continue 'points;
+            }
+        }
+
+        // No convergence, typically because the point is outside of the disc
+        operands.set_xy(i, f64::NAN, f64::NAN);
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 6] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    if lat_0.is_nan() || lat_0.abs() > FRAC_PI_2 {
+        warn!("Ortho: Bad central latitude!");
+        return Err(Error::BadParam("lat_0".to_string(), def.clone()));
+    }
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn ortho() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("ortho ellps=6400000,0")?;
+
+        // Validation values from PROJ builtins.gie, using a sphere with R = 6400 km
+        let geo = [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ];
+
+        let projected = [
+            Coor4D::raw(223_322.760_576_727, 111_695.401_198_614, 0., 0.),
+            Coor4D::raw(223_322.760_576_727, -111_695.401_198_614, 0., 0.),
+            Coor4D::raw(-223_322.760_576_727, 111_695.401_198_614, 0., 0.),
+            Coor4D::raw(-223_322.760_576_727, -111_695.401_198_614, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn far_side() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("ortho lat_0=55 lon_0=5")?;
+
+        let mut operands = [
+            Coor4D::geo(50., 9., 0., 0.),
+            Coor4D::geo(-55., -175., 0., 0.),
+            Coor4D::geo(-40., 5., 0., 0.),
+        ];
+
+        // Only the first point is visible
+        assert_eq!(ctx.apply(op, Fwd, &mut operands)?, 1);
+        assert!(!operands[0][0].is_nan());
+        assert!(operands[1][0].is_nan() && operands[1][1].is_nan());
+        assert!(operands[2][0].is_nan() && operands[2][1].is_nan());
+
+        // Outside of the disc
+        let mut operands = [Coor4D::raw(7e6, 0., 0., 0.)];
+        assert_eq!(ctx.apply(op, Inv, &mut operands)?, 0);
+        assert!(operands[0][0].is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let geo = [
+            Coor4D::geo(85., 10., 0., 0.),
+            Coor4D::geo(60., -60., 0., 0.),
+            Coor4D::geo(30., 90., 0., 0.),
+        ];
+
+        for definition in [
+            "ortho lat_0=90",
+            "ortho lat_0=40 lon_0=10",
+            "ortho lon_0=10",
+        ] {
+            let op = ctx.op(definition)?;
+            let mut operands = geo;
+            ctx.apply(op, Fwd, &mut operands)?;
+            ctx.apply(op, Inv, &mut operands)?;
+            for i in 0..operands.len() {
+                assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-11);
+            }
+        }
+        Ok(())
+    }
+}
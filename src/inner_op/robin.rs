@@ -0,0 +1,270 @@
+//! Robinson: A compromise pseudocylindrical world projection, defined by
+//! tabulated values at 5° intervals of latitude. We use the cubic
+//! interpolation polynomials from the PROJ implementation. The projection is
+//! spherical: the semimajor axis of the ellipsoid is used as radius.
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+// Scaling of the tabulated values
+const FXC: f64 = 0.8487;
+const FYC: f64 = 1.3523;
+
+// Number of table intervals, and their size in radians
+const NODES: usize = 18;
+const INTERVAL: f64 = 5. * std::f64::consts::PI / 180.;
+
+const EPS: f64 = 1e-8;
+const ONE_EPS: f64 = 1.000001;
+const MAX_ITERATIONS: usize = 10;
+
+// Cubic polynomials c0 + c1·z + c2·z² + c3·z³, with z in degrees from the node.
+// X: length of the parallel, Y: distance of the parallel from the equator.
+// PROJ stores the coefficients in single precision, and so do we, in order
+// to reproduce its results exactly
+#[rustfmt::skip]
+const X: [[f32; 4]; NODES + 1] = [
+    [1.0,    2.2199e-17,  -7.15515e-05,  3.1103e-06],
+    [0.9986, -0.000482243, -2.4897e-05,  -1.3309e-06],
+    [0.9954, -0.00083103, -4.48605e-05,  -9.86701e-07],
+    [0.99,   -0.00135364, -5.9661e-05,    3.6777e-06],
+    [0.9822, -0.00167442, -4.49547e-06,  -5.72411e-06],
+    [0.973,  -0.00214868, -9.03571e-05,   1.8736e-08],
+    [0.96,   -0.00305085, -9.00761e-05,   1.64917e-06],
+    [0.9427, -0.00382792, -6.53386e-05,  -2.6154e-06],
+    [0.9216, -0.00467746, -0.00010457,    4.81243e-06],
+    [0.8962, -0.00536223, -3.23831e-05,  -5.43432e-06],
+    [0.8679, -0.00609363, -0.000113898,   3.32484e-06],
+    [0.835,  -0.00698325, -6.40253e-05,   9.34959e-07],
+    [0.7986, -0.00755338, -5.00009e-05,   9.35324e-07],
+    [0.7597, -0.00798324, -3.5971e-05,   -2.27626e-06],
+    [0.7186, -0.00851367, -7.01149e-05,  -8.6303e-06],
+    [0.6732, -0.00986209, -0.000199569,   1.91974e-05],
+    [0.6213, -0.010418,    8.83923e-05,   6.24051e-06],
+    [0.5722, -0.00906601,  0.000182,      6.24051e-06],
+    [0.5322, -0.00677797,  0.000275608,   6.24051e-06],
+];
+
+#[rustfmt::skip]
+const Y: [[f32; 4]; NODES + 1] = [
+    [-5.20417e-18, 0.0124,     1.21431e-18, -8.45284e-11],
+    [0.062,        0.0124,    -1.26793e-09,  4.22642e-10],
+    [0.124,        0.0124,     5.07171e-09, -1.60604e-09],
+    [0.186,        0.0123999, -1.90189e-08,  6.00152e-09],
+    [0.248,        0.0124002,  7.10039e-08, -2.24e-08],
+    [0.31,         0.0123992, -2.64997e-07,  8.35986e-08],
+    [0.372,        0.0124029,  9.88983e-07, -3.11994e-07],
+    [0.434,        0.0123893, -3.69093e-06, -4.35621e-07],
+    [0.4958,       0.0123198, -1.02252e-05, -3.45523e-07],
+    [0.5571,       0.0121916, -1.54081e-05, -5.82288e-07],
+    [0.6176,       0.0119938, -2.41424e-05, -5.25327e-07],
+    [0.6769,       0.011713,  -3.20223e-05, -5.16405e-07],
+    [0.7346,       0.0113541, -3.97684e-05, -6.09052e-07],
+    [0.7903,       0.0109107, -4.89042e-05, -1.04739e-06],
+    [0.8435,       0.0103431, -6.4615e-05,  -1.40374e-09],
+    [0.8936,       0.00969686, -6.4636e-05, -8.547e-06],
+    [0.9394,       0.00840947, -0.000192841, -4.2106e-06],
+    [0.9761,       0.00616527, -0.000256,   -4.2106e-06],
+    [1.0,          0.00328947, -0.000319159, -4.2106e-06],
+];
+
+// The interpolation polynomial, and its derivative
+fn v(c: &[f64; 4], z: f64) -> f64 {
+    c[0] + z * (c[1] + z * (c[2] + z * c[3]))
+}
+
+fn dv(c: &[f64; 4], z: f64) -> f64 {
+    c[1] + z * (2. * c[2] + z * 3. * c[3])
+}
+
+fn coefficients(table: &[[f32; 4]; NODES + 1], i: usize) -> [f64; 4] {
+    table[i].map(f64::from)
+}
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let dphi = lat.abs();
+        if dphi.is_nan() || dphi > FRAC_PI_2 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        // The table interval, and the distance into it, in degrees
+        let n = ((dphi / INTERVAL + 1e-15).floor() as usize).min(NODES);
+        let z = (dphi - INTERVAL * n as f64).to_degrees();
+
+        let lam = angular::normalize_symmetric(lon - lon_0);
+        let x = a * v(&coefficients(&X, n), z) * FXC * lam;
+        let y = a * v(&coefficients(&Y, n), z) * FYC * lat.signum();
+        operands.set_xy(i, x + x_0, y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let a = op.params.ellps(0).semimajor_axis();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    'points: for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = ((x - x_0) / a, (y - y_0) / a);
+        let mut lam = x / FXC;
+        let yy = (y / FYC).abs();
+
+        // The pathological cases: Outside, or at, the poles
+        if yy.is_nan() || yy > ONE_EPS {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        if yy >= 1. {
+            lam /= f64::from(X[NODES][0]);
+            let lon = angular::normalize_symmetric(lam + lon_0);
+            operands.set_xy(i, lon, FRAC_PI_2.copysign(y));
+            successes += 1;
+            continue;
+        }
+
+        // Locate the table interval
+        let mut n = ((yy * NODES as f64).floor() as usize).min(NODES - 1);
+        loop {
+            if f64::from(Y[n][0]) > yy {
+                n -= 1;
+            } else if f64::from(Y[n + 1][0]) <= yy {
+                n += 1;
+            } else {
+                break;
+            }
+        }
+
+        // Linear interpolation as first guess, then Newton-Raphson
+        let mut poly = coefficients(&Y, n);
+        let mut t = 5. * (yy - poly[0]) / (f64::from(Y[n + 1][0]) - poly[0]);
+        poly[0] -= yy;
+        for _ in 0..MAX_ITERATIONS {
+            let dt = v(&poly, t) / dv(&poly, t);
+            t -= dt;
+            if dt.abs() < EPS {
+                let lat = (5. * n as f64 + t).to_radians().copysign(y);
+                lam /= v(&coefficients(&X, n), t);
+                if lam.abs() > std::f64::consts::PI {
+                    break;
+                }
+                operands.set_xy(i, angular::normalize_symmetric(lam + lon_0), lat);
+                successes += 1;
+                continue 'points;
+            }
+        }
+
+        operands.set_xy(i, f64::NAN, f64::NAN);
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 5] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn robin() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("robin ellps=6400000,0")?;
+
+        // Validation values from PROJ builtins.gie, using a sphere with R = 6400 km
+        let geo = [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ];
+
+        let projected = [
+            Coor4D::raw(189_588.423_282_508, 107_318.530_350_703, 0., 0.),
+            Coor4D::raw(189_588.423_282_508, -107_318.530_350_703, 0., 0.),
+            Coor4D::raw(-189_588.423_282_508, 107_318.530_350_703, 0., 0.),
+            Coor4D::raw(-189_588.423_282_508, -107_318.530_350_703, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("robin lon_0=11")?;
+
+        let geo = [
+            Coor4D::geo(0., 11., 0., 0.),
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(-37.5, 150., 0., 0.),
+            Coor4D::geo(72.3, -100., 0., 0.),
+            Coor4D::geo(-89., -168., 0., 0.),
+            Coor4D::geo(90., 0., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, Coor4D::origin().0, abs_all <= 1e-9);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-10);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+//! Sinusoidal (Sanson-Flamsteed): An equal area pseudocylindrical projection.
+//! Ellipsoidal form, following [Snyder (1987)](crate::Bibliography::Sny87), p. 243,
+//! with the meridian distance from the [Meridians](crate::ellps::Meridians) trait
+use crate::authoring::*;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+const EPS10: f64 = 1e-10;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+        let (sin_phi, cos_phi) = lat.sin_cos();
+        let lam = angular::normalize_symmetric(lon - lon_0);
+
+        // Snyder (1987) eqs. 30-8, 30-9
+        let x = a * lam * cos_phi / (1. - es * sin_phi * sin_phi).sqrt();
+        let y = ellps.meridian_latitude_to_distance(lat);
+        operands.set_xy(i, x + x_0, y + y_0);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let es = ellps.eccentricity_squared();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let quadrant = ellps.meridian_quadrant();
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let (x, y) = (x - x_0, y - y_0);
+        if y.abs() > quadrant * (1. + EPS10) {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        // Snyder (1987) eqs. 30-10, 30-11
+        let lat = ellps.meridian_distance_to_latitude(y);
+        let (sin_phi, cos_phi) = lat.sin_cos();
+
+        // At the poles, all longitudes coincide
+        let lam = if FRAC_PI_2 - lat.abs() < EPS10 {
+            0.
+        } else {
+            x * (1. - es * sin_phi * sin_phi).sqrt() / (a * cos_phi)
+        };
+        if lam.abs() > PI * (1. + EPS10) {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+
+        let lon = angular::normalize_symmetric(lam + lon_0);
+        operands.set_xy(i, lon, lat);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 5] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn sinu() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("sinu")?;
+
+        // Validation values from PROJ builtins.gie
+        let geo = [
+            Coor4D::geo(1., 2., 0., 0.),
+            Coor4D::geo(-1., 2., 0., 0.),
+            Coor4D::geo(1., -2., 0., 0.),
+            Coor4D::geo(-1., -2., 0., 0.),
+        ];
+
+        let projected = [
+            Coor4D::raw(222_605.299_539_466, 110_574.388_554_153, 0., 0.),
+            Coor4D::raw(222_605.299_539_466, -110_574.388_554_153, 0., 0.),
+            Coor4D::raw(-222_605.299_539_466, 110_574.388_554_153, 0., 0.),
+            Coor4D::raw(-222_605.299_539_466, -110_574.388_554_153, 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, projected[i].0, abs_all <= 1e-6);
+        }
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn spherical() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("sinu ellps=6400000,0 lon_0=30")?;
+
+        // On the sphere, x = R·Δλ·cos(φ) and y = R·φ
+        let geo = [Coor4D::geo(60., 120., 0., 0.)];
+        let r = 6_400_000.;
+        let expected = [Coor4D::raw(
+            r * 90_f64.to_radians() * 0.5,
+            r * 60_f64.to_radians(),
+            0.,
+            0.,
+        )];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, expected[0].0, abs_all <= 1e-6);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-12);
+        Ok(())
+    }
+}
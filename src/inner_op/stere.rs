@@ -0,0 +1,471 @@
+//! Stereographic projection: Polar (EPSG coordinate operation methods 9810 and 9829),
+//! equatorial and oblique aspects. Follows the PROJ implementation, cf.
+//! <https://proj.org/operations/projections/stere.html>
+use crate::authoring::*;
+use std::f64::consts::FRAC_PI_2;
+
+const EPS10: f64 = 1e-10;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let e = ellps.eccentricity();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(akm1) = op.params.real("akm1") else {
+        return 0;
+    };
+
+    let north_polar = op.params.boolean("north_polar");
+    let south_polar = op.params.boolean("south_polar");
+
+    let mut successes = 0_usize;
+    let n = operands.len();
+
+    // The polar aspects: Snyder (1987) eqs. 21-33 and 21-34
+    if north_polar || south_polar {
+        // Mirror the south polar case onto the north polar
+        let sign = if north_polar { 1.0 } else { -1.0 };
+        for i in 0..n {
+            let (lon, lat) = operands.xy(i);
+            let lat = sign * lat;
+            let (sin_lon, cos_lon) = (lon - lon_0).sin_cos();
+
+            // Cannot project the opposite pole
+            if (lat + FRAC_PI_2).abs() < EPS10 {
+                operands.set_xy(i, f64::NAN, f64::NAN);
+                continue;
+            }
+
+            let rho = akm1 * ancillary::ts(lat.sin_cos(), e);
+            let x = a * rho * sin_lon + x_0;
+            let y = -sign * a * rho * cos_lon + y_0;
+            operands.set_xy(i, x, y);
+            successes += 1;
+        }
+        return successes;
+    }
+
+    // The equatorial and oblique aspects: Snyder (1987) eqs. 21-24 to 21-28,
+    // reckoning the latitudes on the conformal sphere
+    let Ok(sin_chi_0) = op.params.real("sin_chi_0") else {
+        return 0;
+    };
+    let Ok(cos_chi_0) = op.params.real("cos_chi_0") else {
+        return 0;
+    };
+
+    for i in 0..n {
+        let (lon, lat) = operands.xy(i);
+        let (sin_lon, cos_lon) = (lon - lon_0).sin_cos();
+
+        // The conformal latitude, 𝜒
+        let chi = gudermannian::fwd(ellps.latitude_geographic_to_isometric(lat));
+        let (sin_chi, cos_chi) = chi.sin_cos();
+
+        let denom = cos_chi_0 * (1.0 + sin_chi_0 * sin_chi + cos_chi_0 * cos_chi * cos_lon);
+        if denom.abs() < EPS10 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let big_a = akm1 / denom;
+
+        let x = a * big_a * cos_chi * sin_lon + x_0;
+        let y = a * big_a * (cos_chi_0 * sin_chi - sin_chi_0 * cos_chi * cos_lon) + y_0;
+        operands.set_xy(i, x, y);
+        successes += 1;
+    }
+
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let e = ellps.eccentricity();
+    let lon_0 = op.params.lon(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(akm1) = op.params.real("akm1") else {
+        return 0;
+    };
+
+    let north_polar = op.params.boolean("north_polar");
+    let south_polar = op.params.boolean("south_polar");
+
+    let mut successes = 0_usize;
+    let n = operands.len();
+
+    if north_polar || south_polar {
+        let sign = if north_polar { 1.0 } else { -1.0 };
+        for i in 0..n {
+            let (x, y) = operands.xy(i);
+            let x = (x - x_0) / a;
+            let y = -sign * (y - y_0) / a;
+            let rho = x.hypot(y);
+
+            let lat = sign * ancillary::pj_phi2(rho / akm1, e);
+            if lat.is_nan() {
+                operands.set_xy(i, f64::NAN, f64::NAN);
+                continue;
+            }
+
+            let lon = if rho == 0.0 { 0.0 } else { x.atan2(y) };
+            operands.set_xy(i, angular::normalize_symmetric(lon + lon_0), lat);
+            successes += 1;
+        }
+        return successes;
+    }
+
+    let Ok(sin_chi_0) = op.params.real("sin_chi_0") else {
+        return 0;
+    };
+    let Ok(cos_chi_0) = op.params.real("cos_chi_0") else {
+        return 0;
+    };
+
+    for i in 0..n {
+        let (x, y) = operands.xy(i);
+        let x = (x - x_0) / a;
+        let y = (y - y_0) / a;
+        let rho = x.hypot(y);
+
+        // The angular distance from the projection center, on the conformal sphere
+        let c = 2.0 * (rho * cos_chi_0).atan2(akm1);
+        let (sin_c, cos_c) = c.sin_cos();
+
+        // The conformal latitude, 𝜒
+        let chi = if rho == 0.0 {
+            (cos_c * sin_chi_0).asin()
+        } else {
+            (cos_c * sin_chi_0 + y * sin_c * cos_chi_0 / rho).asin()
+        };
+        let lat = ellps.latitude_isometric_to_geographic(gudermannian::inv(chi));
+
+        let num = x * sin_c;
+        let denom = rho * cos_chi_0 * cos_c - y * sin_chi_0 * sin_c;
+        let lon = if num == 0.0 && denom == 0.0 {
+            0.0
+        } else {
+            num.atan2(denom)
+        };
+        operands.set_xy(i, angular::normalize_symmetric(lon + lon_0), lat);
+        successes += 1;
+    }
+
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 8] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps",  default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0",  default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0",  default: Some(0_f64) },
+    OpParameter::Real { key: "lat_ts", default: Some(f64::NAN) },
+
+    OpParameter::Real { key: "k_0",    default: Some(1_f64) },
+    OpParameter::Real { key: "x_0",    default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",    default: Some(0_f64) },
+];
+
+#[rustfmt::skip]
+pub const UPS_GAMUT: [OpParameter; 3] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Flag { key: "south" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    precompute(&mut params, def)?;
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- C O N S T R U C T O R,   U P S ------------------------------------------------
+
+pub fn ups(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &UPS_GAMUT)?;
+
+    // The projection center is the north pole, or the south pole if using the southern aspect
+    params.real.insert("lat_0", 90.);
+    if params.boolean("south") {
+        params.real.insert("lat_0", -90.);
+    }
+
+    // The scaling factor is 0.994 by definition of UPS
+    params.real.insert("k_0", 0.994);
+    params.real.insert("lat_ts", f64::NAN);
+
+    // Grid north is along the Greenwich meridian
+    params.real.insert("lon_0", 0.);
+
+    // The false easting and northing are both 2000000 m by definition of UPS
+    params.real.insert("x_0", 2_000_000.);
+    params.real.insert("y_0", 2_000_000.);
+
+    precompute(&mut params, def)?;
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- A N C I L L A R Y   F U N C T I O N S -----------------------------------------
+
+// Determine the aspect, and precompute the latitude invariant factors
+fn precompute(params: &mut ParsedParameters, def: &str) -> Result<(), Error> {
+    let lat_0 = params.lat(0).to_radians();
+    let lat_ts = params.real("lat_ts")?.to_radians();
+    let k_0 = params.k(0);
+
+    let t = lat_0.abs();
+    if lat_0.is_nan() || t > FRAC_PI_2 + EPS10 {
+        warn!("Stere: Bad central latitude!");
+        return Err(Error::BadParam("lat_0".to_string(), def.to_string()));
+    }
+    if lat_ts.abs() > FRAC_PI_2 + EPS10 {
+        warn!("Stere: Bad latitude of true scale!");
+        return Err(Error::BadParam("lat_ts".to_string(), def.to_string()));
+    }
+
+    let polar = (t - FRAC_PI_2).abs() < EPS10;
+    match (polar, lat_0 > 0.0, t > EPS10) {
+        (true, true, _) => params.boolean.insert("north_polar"),
+        (true, false, _) => params.boolean.insert("south_polar"),
+        (false, _, true) => params.boolean.insert("oblique"),
+        (false, _, false) => params.boolean.insert("equatorial"),
+    };
+
+    let ellps = params.ellps(0);
+    let e = ellps.eccentricity();
+
+    // The polar aspects are scaled either by k_0 (variant A), or by having
+    // true scale along the parallel lat_ts (variant B)
+    if polar {
+        let lat_ts = if lat_ts.is_nan() {
+            FRAC_PI_2
+        } else {
+            lat_ts.abs()
+        };
+        let akm1 = if (lat_ts - FRAC_PI_2).abs() < EPS10 {
+            2.0 * k_0 / ((1.0 + e).powf(1.0 + e) * (1.0 - e).powf(1.0 - e)).sqrt()
+        } else {
+            let sc = lat_ts.sin_cos();
+            ancillary::pj_msfn(sc, e * e) / ancillary::ts(sc, e)
+        };
+        params.real.insert("akm1", akm1);
+    } else {
+        let chi_0 = gudermannian::fwd(ellps.latitude_geographic_to_isometric(lat_0));
+        let (sin_chi_0, cos_chi_0) = chi_0.sin_cos();
+        let akm1 = 2.0 * k_0 * ancillary::pj_msfn(lat_0.sin_cos(), e * e);
+        params.real.insert("akm1", akm1);
+        params.real.insert("sin_chi_0", sin_chi_0);
+        params.real.insert("cos_chi_0", cos_chi_0);
+    }
+
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+    Ok(())
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn polar_variant_a() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // Test point from IOGP Guidance Note 7-2, pp. 87-88 (EPSG method 9810),
+        // i.e. EPSG:32661, WGS 84 / UPS North
+        let op = ctx.op("stere lat_0=90 lon_0=0 k_0=0.994 x_0=2000000 y_0=2000000 ellps=WGS84")?;
+
+        let geo = [Coor4D::geo(73., 44., 0., 0.)];
+        let projected = [Coor4D::raw(3_320_416.75, 632_668.43, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, projected[0].0, abs_all <= 0.01);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn polar_variant_b() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // Test point from IOGP Guidance Note 7-2, pp. 88-89 (EPSG method 9829)
+        let op =
+            ctx.op("stere lat_0=-90 lat_ts=-71 lon_0=70 x_0=6000000 y_0=6000000 ellps=WGS84")?;
+
+        let geo = [Coor4D::geo(-75., 120., 0., 0.)];
+        let projected = [Coor4D::raw(7_255_380.79, 7_053_389.56, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, projected[0].0, abs_all <= 0.01);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn north_polar() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:3413, NSIDC Sea Ice Polar Stereographic North
+        let op = ctx.op("stere lat_0=90 lat_ts=70 lon_0=-45 ellps=WGS84")?;
+
+        // On the central meridian, the northing is the negated distance from the pole,
+        // and at the true scale latitude, the scale is unity
+        let geo = [
+            Coor4D::geo(69.9995, -45., 0., 0.),
+            Coor4D::geo(70.0005, -45., 0., 0.),
+            Coor4D::geo(60., 45., 0., 0.),
+            Coor4D::geo(85., 100., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert!(operands[0][0].abs() < 1e-9);
+        assert!(operands[0][1] < 0.);
+        assert!(operands[2][0] > 0.);
+        assert_float_eq!(operands[2][1], 0.0, abs <= 1e-9);
+
+        let ellps = Ellipsoid::named("WGS84")?;
+        let ds = operands[1][1] - operands[0][1];
+        let dm = ellps.meridian_latitude_to_distance(70.0005_f64.to_radians())
+            - ellps.meridian_latitude_to_distance(69.9995_f64.to_radians());
+        assert!((ds / dm - 1.).abs() < 1e-9);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn oblique() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("stere lat_0=52 lon_0=5 k_0=0.9999 x_0=155000 y_0=463000 ellps=bessel")?;
+
+        let geo = [
+            Coor4D::geo(52., 5., 0., 0.),
+            Coor4D::geo(53., 6., 0., 0.),
+            Coor4D::geo(45., -10., 0., 0.),
+            Coor4D::geo(-20., 100., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+
+        // The projection center maps to the false origin
+        assert_float_eq!(operands[0][0], 155_000., abs <= 1e-9);
+        assert_float_eq!(operands[0][1], 463_000., abs <= 1e-9);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn equatorial() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("stere lon_0=20")?;
+
+        let geo = [
+            Coor4D::geo(0., 20., 0., 0.),
+            Coor4D::geo(10., 30., 0., 0.),
+            Coor4D::geo(-10., 30., 0., 0.),
+            Coor4D::geo(60., -50., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert!(operands[0][0].abs() < 1e-9);
+        assert!(operands[0][1].abs() < 1e-9);
+        assert_float_eq!(operands[1][0], operands[2][0], abs <= 1e-9);
+        assert_float_eq!(operands[1][1], -operands[2][1], abs <= 1e-9);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn ups() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // UPS North is identical to the IOGP example for variant A
+        let op = ctx.op("ups ellps=WGS84")?;
+        let geo = [Coor4D::geo(73., 44., 0., 0.)];
+        let projected = [Coor4D::raw(3_320_416.75, 632_668.43, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, projected[0].0, abs_all <= 0.01);
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-12);
+
+        // UPS South mirrors UPS North, with grid north still along the Greenwich meridian
+        let op = ctx.op("ups south ellps=WGS84")?;
+        let mut operands = [Coor4D::geo(-73., 44., 0., 0.)];
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], projected[0][0], abs <= 0.01);
+        assert_float_eq!(operands[0][1], 4e6 - projected[0][1], abs <= 0.01);
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0][0], 44_f64.to_radians(), abs <= 1e-12);
+        assert_float_eq!(operands[0][1], -73_f64.to_radians(), abs <= 1e-12);
+
+        // The poles map to the false origin
+        let mut operands = [Coor4D::geo(-90., 0., 0., 0.)];
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], 2e6, abs <= 1e-9);
+        assert_float_eq!(operands[0][1], 2e6, abs <= 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_parameters() {
+        let mut ctx = Minimal::default();
+        assert!(ctx.op("stere lat_0=91").is_err());
+        assert!(ctx.op("stere lat_0=90 lat_ts=100").is_err());
+    }
+}
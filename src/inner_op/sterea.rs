@@ -0,0 +1,239 @@
+//! Oblique Stereographic Alternative: The Gauss-Schreiber double projection, i.e.
+//! EPSG coordinate operation method 9809, implemented following
+//! [IOGP, 2019](crate::Bibliography::Iogp19), pp. 84-86, and the PROJ implementation,
+//! cf. <https://proj.org/operations/projections/sterea.html>
+use crate::authoring::*;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+const MAX_ITERATIONS: usize = 20;
+const DEL_TOL: f64 = 1e-14;
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let e = ellps.eccentricity();
+    let lon_0 = op.params.lon(0);
+    let k_0 = op.params.k(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(c) = op.params.real("c") else { return 0 };
+    let Ok(k) = op.params.real("k") else { return 0 };
+    let Ok(r2) = op.params.real("r2") else {
+        return 0;
+    };
+    let Ok(chi_0) = op.params.real("chi_0") else {
+        return 0;
+    };
+    let (sin_chi_0, cos_chi_0) = chi_0.sin_cos();
+    let ratexp = 0.5 * c * e;
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let (lon, lat) = operands.xy(i);
+
+        // Geographic coordinates to the conformal (Gaussian) sphere
+        let chi = 2.0
+            * (k * (0.5 * lat + FRAC_PI_4).tan().powf(c) * srat(e * lat.sin(), ratexp)).atan()
+            - FRAC_PI_2;
+        let lam = c * angular::normalize_symmetric(lon - lon_0);
+
+        // ... and the stereographic projection of the sphere
+        let (sin_chi, cos_chi) = chi.sin_cos();
+        let (sin_lam, cos_lam) = lam.sin_cos();
+        let denom = 1.0 + sin_chi_0 * sin_chi + cos_chi_0 * cos_chi * cos_lam;
+        if denom == 0.0 {
+            operands.set_xy(i, f64::NAN, f64::NAN);
+            continue;
+        }
+        let scale = a * k_0 * r2 / denom;
+
+        let x = scale * cos_chi * sin_lam + x_0;
+        let y = scale * (cos_chi_0 * sin_chi - sin_chi_0 * cos_chi * cos_lam) + y_0;
+        operands.set_xy(i, x, y);
+        successes += 1;
+    }
+    successes
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let ellps = op.params.ellps(0);
+    let a = ellps.semimajor_axis();
+    let e = ellps.eccentricity();
+    let lon_0 = op.params.lon(0);
+    let k_0 = op.params.k(0);
+    let x_0 = op.params.x(0);
+    let y_0 = op.params.y(0);
+    let Ok(c) = op.params.real("c") else { return 0 };
+    let Ok(k) = op.params.real("k") else { return 0 };
+    let Ok(r2) = op.params.real("r2") else {
+        return 0;
+    };
+    let Ok(chi_0) = op.params.real("chi_0") else {
+        return 0;
+    };
+    let (sin_chi_0, cos_chi_0) = chi_0.sin_cos();
+
+    let mut successes = 0_usize;
+    'points: for i in 0..operands.len() {
+        let (x, y) = operands.xy(i);
+        let x = (x - x_0) / (a * k_0);
+        let y = (y - y_0) / (a * k_0);
+
+        // The inverse stereographic projection of the conformal sphere
+        let rho = x.hypot(y);
+        let (chi, lam) = if rho == 0.0 {
+            (chi_0, 0.0)
+        } else {
+            let (sin_c, cos_c) = (2.0 * rho.atan2(r2)).sin_cos();
+            let chi = (cos_c * sin_chi_0 + y * sin_c * cos_chi_0 / rho).asin();
+            let lam = (x * sin_c).atan2(rho * cos_chi_0 * cos_c - y * sin_chi_0 * sin_c);
+            (chi, lam)
+        };
+
+        // ... and back from the sphere to the ellipsoid
+        let num = ((0.5 * chi + FRAC_PI_4).tan() / k).powf(1.0 / c);
+        let mut lat = chi;
+        for _ in 0..MAX_ITERATIONS {
+            let next = 2.0 * (num * srat(e * lat.sin(), -0.5 * e)).atan() - FRAC_PI_2;
+            if (next - lat).abs() < DEL_TOL {
+                let lon = angular::normalize_symmetric(lam / c + lon_0);
+                operands.set_xy(i, lon, next);
+                successes += 1;
+                continue 'points;
+            }
+            lat = next;
+        }
+
+        debug!("Sterea: No convergence for ({x}, {y})");
+        operands.set_xy(i, f64::NAN, f64::NAN);
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 7] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+
+    OpParameter::Real { key: "lat_0", default: Some(0_f64) },
+    OpParameter::Real { key: "lon_0", default: Some(0_f64) },
+
+    OpParameter::Real { key: "k_0",   default: Some(1_f64) },
+    OpParameter::Real { key: "x_0",   default: Some(0_f64) },
+    OpParameter::Real { key: "y_0",   default: Some(0_f64) },
+];
+
+pub fn new(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    let lat_0 = params.lat(0).to_radians();
+    if lat_0.is_nan() || lat_0.abs() > FRAC_PI_2 {
+        warn!("Sterea: Bad central latitude!");
+        return Err(Error::BadParam("lat_0".to_string(), def.clone()));
+    }
+
+    // The parameters of the conformal (Gaussian) sphere
+    let ellps = params.ellps(0);
+    let e = ellps.eccentricity();
+    let es = ellps.eccentricity_squared();
+    let (sin_phi_0, cos_phi_0) = lat_0.sin_cos();
+
+    // Radius of the sphere, in units of the semimajor axis
+    let r = (1.0 - es).sqrt() / (1.0 - es * sin_phi_0 * sin_phi_0);
+    let c = (1.0 + es * cos_phi_0.powi(4) / (1.0 - es)).sqrt();
+    let chi_0 = (sin_phi_0 / c).asin();
+    let k = if 0.5 * lat_0 + FRAC_PI_4 < 1e-10 {
+        1.0 / srat(e * sin_phi_0, 0.5 * c * e)
+    } else {
+        (0.5 * chi_0 + FRAC_PI_4).tan()
+            / ((0.5 * lat_0 + FRAC_PI_4).tan().powf(c) * srat(e * sin_phi_0, 0.5 * c * e))
+    };
+
+    params.real.insert("c", c);
+    params.real.insert("k", k);
+    params.real.insert("r2", 2.0 * r);
+    params.real.insert("chi_0", chi_0);
+    params.real.insert("lat_0", lat_0);
+    params.real.insert("lon_0", params.lon(0).to_radians());
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- A N C I L L A R Y   F U N C T I O N S -----------------------------------------
+
+// PROJ's `srat`, from gauss.cpp
+fn srat(esinp: f64, ratexp: f64) -> f64 {
+    ((1.0 - esinp) / (1.0 + esinp)).powf(ratexp)
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn rd_new() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // EPSG:28992, Amersfoort / RD New
+        let op = ctx.op(
+            "sterea lat_0=52:09:22.178 lon_0=5:23:15.5 k_0=0.9999079 x_0=155000 y_0=463000 ellps=bessel",
+        )?;
+
+        // Test point from IOGP Guidance Note 7-2, p. 86
+        let geo = [Coor4D::geo(53., 6., 0., 0.)];
+        let projected = [Coor4D::raw(196_105.283, 557_057.739, 0., 0.)];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0].0, projected[0].0, abs_all <= 1e-3);
+
+        ctx.apply(op, Inv, &mut operands)?;
+        assert_float_eq!(operands[0].0, geo[0].0, abs_all <= 1e-12);
+
+        // And the origin maps to the false origin
+        let mut operands = [Coor4D::geo(52.156160556, 5.387638889, 0., 0.)];
+        ctx.apply(op, Fwd, &mut operands)?;
+        assert_float_eq!(operands[0][0], 155_000., abs <= 1e-3);
+        assert_float_eq!(operands[0][1], 463_000., abs <= 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let op = ctx.op("sterea lat_0=-40 lon_0=170 k_0=0.9999")?;
+
+        let geo = [
+            Coor4D::geo(-40., 170., 0., 0.),
+            Coor4D::geo(-35., 175., 0., 0.),
+            Coor4D::geo(-47., 166., 0., 0.),
+            Coor4D::geo(10., -170., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(op, Fwd, &mut operands)?;
+        ctx.apply(op, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+}
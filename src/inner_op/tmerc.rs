@@ -184,6 +184,14 @@ pub const UTM_GAMUT: [OpParameter; 4] = [
     OpParameter::Natural { key: "zone", default: None },
 ];
 
+#[rustfmt::skip]
+pub const GK_GAMUT: [OpParameter; 4] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+    OpParameter::Natural { key: "zone", default: None },
+    OpParameter::Natural { key: "width", default: Some(6) },
+];
+
 // ----- C O N S T R U C T O R,   U T M ------------------------------------------------
 
 pub fn utm(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
@@ -233,6 +241,67 @@ pub fn utm(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error>
     Ok(op)
 }
 
+// ----- C O N S T R U C T O R,   G A U S S - K R Ü G E R ------------------------------
+
+pub fn gk(parameters: &RawParameters, _ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GK_GAMUT)?;
+
+    // The zone width is either 3 or 6 degrees
+    let width = params.natural("width")?;
+    if width != 3 && width != 6 {
+        error!("GK: {width}. Zone width must be 3 or 6 degrees");
+        return Err(Error::General("GK: 'width' must be 3 or 6"));
+    }
+
+    // The zones cover the full circle, counting eastwards from Greenwich
+    let zone = params.natural("zone")?;
+    let zones = 360 / width;
+    if !(1..=zones).contains(&zone) {
+        error!("GK: {zone}. Must be an integer in the interval 1..{zones}");
+        return Err(Error::General(
+            "GK: 'zone' must be an integer in the interval 1..60 (6°) or 1..120 (3°)",
+        ));
+    }
+
+    // Gauss-Krüger is unscaled, and based on the equator
+    params.real.insert("k_0", 1.);
+    params.real.insert("lat_0", 0.);
+    params.real.insert("y_0", 0.);
+
+    // The center meridian of a 3° zone is at 3°×zone, of a 6° zone
+    // at 6°×zone - 3°, i.e. at the center of the UTM zone with the
+    // same number, counted from Greenwich rather than from the antimeridian
+    let mut lon_0 = if width == 3 {
+        3. * zone as f64
+    } else {
+        6. * zone as f64 - 3.
+    };
+    if lon_0 > 180. {
+        lon_0 -= 360.;
+    }
+    params.real.insert("lon_0", lon_0);
+
+    // The false easting encodes the zone number in the millions digit(s)
+    params
+        .real
+        .insert("x_0", zone as f64 * 1_000_000. + 500_000.);
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let steps = Vec::<Op>::new();
+    let id = OpHandle::new();
+
+    let mut op = Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    };
+
+    precompute(&mut op);
+    Ok(op)
+}
+
 // ----- A N C I L L A R Y   F U N C T I O N S -----------------------------------------
 
 #[rustfmt::skip]
@@ -435,4 +504,49 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn gk() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // 3° zones: DHDN / 3-degree Gauss-Kruger zone 4 (EPSG:31468)
+        let gk = ctx.op("gk zone=4 width=3 ellps=bessel")?;
+        let tm = ctx.op("tmerc lon_0=12 k_0=1 x_0=4500000 ellps=bessel")?;
+        let geo = [Coor2D::geo(55., 12.), Coor2D::geo(48., 10.5)];
+
+        let mut operands = geo;
+        let mut expected = geo;
+        ctx.apply(gk, Fwd, &mut operands)?;
+        ctx.apply(tm, Fwd, &mut expected)?;
+        assert_eq!(operands, expected);
+
+        ctx.apply(gk, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+
+        // 6° zones: Pulkovo 1942 / Gauss-Kruger zone 4 (EPSG:28404)
+        let gk = ctx.op("gk zone=4 ellps=krass")?;
+        let tm = ctx.op("tmerc lon_0=21 k_0=1 x_0=4500000 ellps=krass")?;
+        let mut operands = geo;
+        let mut expected = geo;
+        ctx.apply(gk, Fwd, &mut operands)?;
+        ctx.apply(tm, Fwd, &mut expected)?;
+        assert_eq!(operands, expected);
+
+        // Zones beyond 180° are mapped to the western hemisphere
+        let gk = ctx.op("gk zone=40 width=6")?;
+        let tm = ctx.op("tmerc lon_0=-123 k_0=1 x_0=40500000")?;
+        let mut operands = [Coor2D::geo(49., -123.)];
+        let mut expected = operands;
+        ctx.apply(gk, Fwd, &mut operands)?;
+        ctx.apply(tm, Fwd, &mut expected)?;
+        assert_eq!(operands, expected);
+
+        assert!(ctx.op("gk").is_err());
+        assert!(ctx.op("gk zone=61").is_err());
+        assert!(ctx.op("gk zone=120 width=3").is_ok());
+        assert!(ctx.op("gk zone=4 width=4").is_err());
+        Ok(())
+    }
 }
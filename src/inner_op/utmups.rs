@@ -0,0 +1,406 @@
+//! Combined UTM/UPS: Select UTM or UPS per coordinate, following the MGRS
+//! convention as implemented in GeographicLib's `UTMUPS` class, i.e. UPS
+//! north of 84°N and south of 80°S, and UTM in between.
+//!
+//! Projected coordinates carry no information about hemisphere and zone, so
+//! to keep the operator invertible, the hemisphere is fixed by `south`, and
+//! the zone by `zone`. Points outside of these are rejected, rather than
+//! projected into coordinates which the inverse would misinterpret.
+use crate::authoring::*;
+
+// Latitude limits of the UTM system
+const NORTH_LIMIT: f64 = 84.;
+const SOUTH_LIMIT: f64 = -80.;
+
+// UTM eastings are limited to 500 km on either side of the central meridian,
+// which is ample for the standard zones, but keeps the UTM coordinates well
+// clear of the UPS limit circles, where all eastings exceed 1300 km
+const MIN_EASTING: f64 = 0.;
+const MAX_EASTING: f64 = 1_000_000.;
+
+// The steps, in the order built by the constructor. The UTM steps are for
+// the given zone, or for zone 1 if none is given: Other zones are handled by
+// shifting the longitude
+const UTM_NORTH: usize = 0;
+const UTM_SOUTH: usize = 1;
+const UPS_NORTH: usize = 2;
+const UPS_SOUTH: usize = 3;
+
+// The standard UTM zone of a point, including the Norway and Svalbard
+// exceptions, cf. `UTMUPS::StandardZone` in GeographicLib
+fn standard_zone(lon: f64, lat: f64) -> usize {
+    // Round to nano-degrees, to keep points on zone and band boundaries from
+    // slipping to the wrong side when converted from radians
+    let lon = (angular::normalize_symmetric(lon).to_degrees() * 1e9).round() / 1e9;
+    let lat = (lat.to_degrees() * 1e9).round() / 1e9;
+    let ilon = (lon.floor() as i64).clamp(-180, 179);
+    let mut zone = (ilon + 186) / 6;
+    let band = ((lat.floor() as i64 + 80) / 8 - 10).clamp(-10, 9);
+    if band == 7 && zone == 31 && ilon >= 3 {
+        zone = 32;
+    } else if band == 9 && (0..42).contains(&ilon) {
+        zone = 2 * ((ilon + 183) / 12) + 1;
+    }
+    zone as usize
+}
+
+// Longitude offset from the zone of the UTM steps to UTM zone `zone`
+fn zone_offset(op: &Op, zone: usize) -> f64 {
+    let base = op.params.natural("zone").unwrap_or(0).max(1);
+    (6. * (zone as f64 - base as f64)).to_radians()
+}
+
+// ----- F O R W A R D -----------------------------------------------------------------
+
+fn fwd(op: &Op, ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    if op.steps.len() != 4 {
+        return 0;
+    }
+    let Ok(zone) = op.params.natural("zone") else {
+        return 0;
+    };
+    let south = op.params.boolean("south");
+    let (utm, ups) = if south {
+        (UTM_SOUTH, UPS_SOUTH)
+    } else {
+        (UTM_NORTH, UPS_NORTH)
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let mut coord = [operands.get_coord(i)];
+        let (lon, lat) = operands.xy(i);
+
+        // Points in the other hemisphere cannot be told apart after projection
+        if (south && lat > 0.) || (!south && lat < 0.) {
+            operands.set_coord(i, &Coor4D::nan());
+            continue;
+        }
+
+        // UPS in the polar caps
+        if lat >= NORTH_LIMIT.to_radians() || lat < SOUTH_LIMIT.to_radians() {
+            successes += op.steps[ups].apply(ctx, &mut coord, Fwd);
+            operands.set_coord(i, &coord[0]);
+            continue;
+        }
+
+        // UTM everywhere else, in the given zone, or the standard one
+        let zone = if zone == 0 {
+            standard_zone(lon, lat)
+        } else {
+            zone
+        };
+        coord[0][0] = angular::normalize_symmetric(lon - zone_offset(op, zone));
+        if op.steps[utm].apply(ctx, &mut coord, Fwd) == 0 || !within_zone(coord[0][0]) {
+            operands.set_coord(i, &Coor4D::nan());
+            continue;
+        }
+        successes += 1;
+        operands.set_coord(i, &coord[0]);
+    }
+    successes
+}
+
+// Is the UTM easting `x` within the limits of the zone?
+fn within_zone(x: f64) -> bool {
+    (MIN_EASTING..=MAX_EASTING).contains(&x)
+}
+
+// ----- I N V E R S E -----------------------------------------------------------------
+
+fn inv(op: &Op, ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    if op.steps.len() != 4 {
+        return 0;
+    }
+    let Ok(zone) = op.params.natural("zone") else {
+        return 0;
+    };
+
+    let south = op.params.boolean("south");
+    let (utm, ups, radius) = if south {
+        (UTM_SOUTH, UPS_SOUTH, op.params.real("south_radius"))
+    } else {
+        (UTM_NORTH, UPS_NORTH, op.params.real("north_radius"))
+    };
+    let Ok(radius) = radius else {
+        return 0;
+    };
+
+    let mut successes = 0_usize;
+    for i in 0..operands.len() {
+        let mut coord = [operands.get_coord(i)];
+
+        // Anything inside the UPS limit circle is UPS. Since the UTM eastings
+        // are limited, UTM coordinates never get anywhere near that circle
+        let (x, y) = operands.xy(i);
+        if (x - UPS_FALSE_ORIGIN).hypot(y - UPS_FALSE_ORIGIN) <= radius {
+            successes += op.steps[ups].apply(ctx, &mut coord, Inv);
+            operands.set_coord(i, &coord[0]);
+            continue;
+        }
+
+        // Without a given zone, UTM coordinates cannot be inverted
+        if zone == 0 || !within_zone(x) {
+            operands.set_coord(i, &Coor4D::nan());
+            continue;
+        }
+        successes += op.steps[utm].apply(ctx, &mut coord, Inv);
+        coord[0][0] = angular::normalize_symmetric(coord[0][0] + zone_offset(op, zone));
+        operands.set_coord(i, &coord[0]);
+    }
+    successes
+}
+
+// ----- C O N S T R U C T O R ---------------------------------------------------------
+
+const UPS_FALSE_ORIGIN: f64 = 2_000_000.;
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 4] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Flag { key: "south" },
+    OpParameter::Text { key: "ellps", default: Some("GRS80") },
+    OpParameter::Natural { key: "zone", default: Some(0) },
+];
+
+pub fn new(parameters: &RawParameters, ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+
+    // Zone 0 means "the standard zone of each point"
+    let zone = params.natural("zone")?;
+    if zone > 60 {
+        error!("UTMUPS: {zone}. Must be an integer in the interval 1..60");
+        return Err(Error::General(
+            "UTMUPS: 'zone' must be an integer in the interval 1..60",
+        ));
+    }
+    let ellps = params.text("ellps")?;
+
+    // The projections we select from
+    let base = zone.max(1);
+    let steps = [
+        format!("utm zone={base} ellps={ellps}"),
+        format!("utm zone={base} ellps={ellps} south"),
+        format!("ups ellps={ellps}"),
+        format!("ups ellps={ellps} south"),
+    ]
+    .iter()
+    .map(|step| Op::op(parameters.next(step), ctx))
+    .collect::<Result<Vec<Op>, Error>>()?;
+
+    // The radii of the UPS limit circles, as seen from the UPS false origin.
+    // We add a micrometer of slack to keep points right on the limits in UPS
+    for (step, limit, key) in [
+        (UPS_NORTH, NORTH_LIMIT, "north_radius"),
+        (UPS_SOUTH, SOUTH_LIMIT, "south_radius"),
+    ] {
+        let mut boundary = [Coor2D::geo(limit, 0.)];
+        steps[step].apply(ctx, &mut boundary, Fwd);
+        let radius = (boundary[0][1] - UPS_FALSE_ORIGIN).abs() + 1e-6;
+        params.real.insert(key, radius);
+    }
+
+    let descriptor = OpDescriptor::new(def, InnerOp(fwd), Some(InnerOp(inv)));
+    let id = OpHandle::new();
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ---------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn north() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let utmups = ctx.op("utmups zone=33 ellps=WGS84")?;
+        let utm = ctx.op("utm zone=33 ellps=WGS84")?;
+        let ups = ctx.op("ups ellps=WGS84")?;
+
+        let geo = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(83.9, 15., 0., 0.),
+            Coor4D::geo(84., 15., 0., 0.),
+            Coor4D::geo(89., 100., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(utmups, Fwd, &mut operands)?;
+
+        let mut expected_utm = [geo[0], geo[1]];
+        ctx.apply(utm, Fwd, &mut expected_utm)?;
+        let mut expected_ups = [geo[2], geo[3]];
+        ctx.apply(ups, Fwd, &mut expected_ups)?;
+        let expected = [
+            expected_utm[0],
+            expected_utm[1],
+            expected_ups[0],
+            expected_ups[1],
+        ];
+
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, expected[i].0, abs_all <= 1e-9);
+        }
+
+        ctx.apply(utmups, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn south() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let utmups = ctx.op("utmups zone=33 south")?;
+        let utm = ctx.op("utm zone=33 south")?;
+        let ups = ctx.op("ups south")?;
+
+        let geo = [
+            Coor4D::geo(-79.9, 15., 0., 0.),
+            Coor4D::geo(-80.1, 12., 0., 0.),
+        ];
+
+        let mut operands = geo;
+        ctx.apply(utmups, Fwd, &mut operands)?;
+
+        let mut expected_utm = [geo[0]];
+        ctx.apply(utm, Fwd, &mut expected_utm)?;
+        let mut expected_ups = [geo[1]];
+        ctx.apply(ups, Fwd, &mut expected_ups)?;
+        assert_float_eq!(operands[0].0, expected_utm[0].0, abs_all <= 1e-9);
+        assert_float_eq!(operands[1].0, expected_ups[0].0, abs_all <= 1e-9);
+
+        ctx.apply(utmups, Inv, &mut operands)?;
+        for i in 0..operands.len() {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+    #[test]
+    fn hemisphere() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // The hemisphere is given by `south`, in both directions
+        let utmups = ctx.op("utmups zone=33 south")?;
+        let ups_south = ctx.op("ups south")?;
+        let utm_south = ctx.op("utm zone=33 south")?;
+
+        let geo = [
+            Coor4D::geo(-85., 10., 0., 0.),
+            Coor4D::geo(-30., 15., 0., 0.),
+            Coor4D::geo(30., 15., 0., 0.),
+            Coor4D::geo(85., 10., 0., 0.),
+        ];
+        let mut operands = geo;
+        assert_eq!(ctx.apply(utmups, Fwd, &mut operands)?, 2);
+        let mut expected = [geo[0]];
+        ctx.apply(ups_south, Fwd, &mut expected)?;
+        assert_float_eq!(operands[0].0, expected[0].0, abs_all <= 1e-9);
+        let mut expected = [geo[1]];
+        ctx.apply(utm_south, Fwd, &mut expected)?;
+        assert_float_eq!(operands[1].0, expected[0].0, abs_all <= 1e-9);
+
+        // Points in the northern hemisphere are rejected
+        assert!(operands[2][0].is_nan());
+        assert!(operands[3][0].is_nan());
+
+        assert_eq!(ctx.apply(utmups, Inv, &mut operands)?, 2);
+        for i in 0..2 {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+
+        // Mixed hemispheres and zones, through a single operator
+        let geo = [
+            Coor4D::geo(55., 12., 0., 0.),
+            Coor4D::geo(55., 9., 0., 0.),
+            Coor4D::geo(83., 20., 0., 0.),
+            Coor4D::geo(88., -100., 0., 0.),
+            Coor4D::geo(-30., 15., 0., 0.),
+            Coor4D::geo(-85., 15., 0., 0.),
+            Coor4D::geo(60., 100., 0., 0.),
+            Coor4D::geo(0., 15., 0., 0.),
+        ];
+        let utmups = ctx.op("utmups zone=33")?;
+        let mut operands = geo;
+        assert_eq!(ctx.apply(utmups, Fwd, &mut operands)?, 5);
+
+        // Points in the south, and far outside the zone, are rejected...
+        for i in [4, 5, 6] {
+            assert!(operands[i][0].is_nan());
+        }
+
+        // ...while all others survive the round trip
+        assert_eq!(ctx.apply(utmups, Inv, &mut operands)?, 5);
+        for i in [0, 1, 2, 3, 7] {
+            assert_float_eq!(operands[i].0, geo[i].0, abs_all <= 1e-12);
+        }
+        for i in [4, 5, 6] {
+            assert!(operands[i][0].is_nan());
+        }
+
+        // UTM coordinates outside the zone limits are not inverted
+        let mut operands = [Coor4D::raw(1_200_000., 1_500_000., 0., 0.)];
+        assert_eq!(ctx.apply(utmups, Inv, &mut operands)?, 0);
+        assert!(operands[0][0].is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn standard_zones() -> Result<(), Error> {
+        let mut ctx = Minimal::default();
+        let utmups = ctx.op("utmups")?;
+
+        assert_eq!(standard_zone(12f64.to_radians(), 55f64.to_radians()), 33);
+        assert_eq!(standard_zone((-177f64).to_radians(), 0.), 1);
+        assert_eq!(standard_zone(179.5f64.to_radians(), 0.), 60);
+        // Norway and Svalbard
+        assert_eq!(standard_zone(5f64.to_radians(), 60f64.to_radians()), 32);
+        assert_eq!(standard_zone(8f64.to_radians(), 78f64.to_radians()), 31);
+        assert_eq!(standard_zone(15f64.to_radians(), 78f64.to_radians()), 33);
+
+        let geo = [
+            (Coor4D::geo(55., 12., 0., 0.), "utm zone=33"),
+            (Coor4D::geo(60., 5., 0., 0.), "utm zone=32"),
+            (Coor4D::geo(-30., -179., 0., 0.), "utm zone=1 south"),
+            (Coor4D::geo(-30., 179., 0., 0.), "utm zone=60 south"),
+        ];
+        let utmups_south = ctx.op("utmups south")?;
+        for (point, definition) in geo {
+            let mut operands = [point];
+            let op = if point[1] < 0. { utmups_south } else { utmups };
+            ctx.apply(op, Fwd, &mut operands)?;
+            let utm = ctx.op(definition)?;
+            let mut expected = [point];
+            ctx.apply(utm, Fwd, &mut expected)?;
+            // Shifting the longitude between zones costs a few nanometres
+            assert_float_eq!(operands[0].0, expected[0].0, abs_all <= 1e-8);
+        }
+
+        // Without a zone, only the UPS coordinates can be inverted
+        let mut operands = [Coor4D::geo(55., 12., 0., 0.), Coor4D::geo(89., 12., 0., 0.)];
+        ctx.apply(utmups, Fwd, &mut operands)?;
+        assert_eq!(ctx.apply(utmups, Inv, &mut operands)?, 1);
+        assert!(operands[0][0].is_nan());
+        assert_float_eq!(
+            operands[1].0,
+            Coor4D::geo(89., 12., 0., 0.).0,
+            abs_all <= 1e-12
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+/// Vertical datum shift using geoid model grid interpolation.
+///
+/// In the forward direction, the interpolated grid value, N, scaled by
+/// the `multiplier`, is added to the height:
+///
+/// |   h' = h + multiplier * N
+///
+/// With the default multiplier of -1, this converts ellipsoidal heights
+/// to orthometric heights when the grid is a geoid model.
+///
+/// Following PROJ, the operation can be made into a step function in
+/// time, i.e. a time dependent geoid, by giving both `t_epoch` and
+/// `t_final`: The shift is then applied only to coordinates observed
+/// before `t_epoch` (as given by their 4th coordinate), when `t_final`
+/// is after `t_epoch`. All other coordinates pass through unchanged.
+use crate::authoring::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ----- F O R W A R D --------------------------------------------------------------
+
+fn fwd(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let multiplier = op.params.real("multiplier").unwrap_or(-1.);
+    shift(op, operands, multiplier)
+}
+
+// ----- I N V E R S E --------------------------------------------------------------
+
+fn inv(op: &Op, _ctx: &dyn Context, operands: &mut dyn CoordinateSet) -> usize {
+    let multiplier = op.params.real("multiplier").unwrap_or(-1.);
+    shift(op, operands, -multiplier)
+}
+
+// The vertical shift is its own inverse, save for the sign of the multiplier
+fn shift(op: &Op, operands: &mut dyn CoordinateSet, multiplier: f64) -> usize {
+    let grids = &op.params.grids;
+    let use_null_grid = op.params.boolean("null_grid");
+    let method = interpolation(op);
+    let t_epoch = op.params.real("t_epoch").unwrap_or(f64::NAN);
+    let t_final = op.params.real("t_final").unwrap_or(f64::NAN);
+
+    let mut successes = 0_usize;
+    let n = operands.len();
+
+    // Nothing to do?
+    if grids.is_empty() {
+        return n;
+    }
+
+    for i in 0..n {
+        let mut coord = operands.get_coord(i);
+
+        // Outside of the temporal gate, the coordinate passes through unchanged
+        if !in_temporal_gate(coord[3], t_epoch, t_final) {
+            successes += 1;
+            continue;
+        }
+
+        if let Some(d) = grids_at_with(grids, &coord, use_null_grid, method) {
+            coord[2] += multiplier * d[0];
+            operands.set_coord(i, &coord);
+            successes += 1;
+            continue;
+        }
+
+        // No grid contained the point, so we stomp on the coordinate
+        operands.set_coord(i, &Coor4D::nan());
+    }
+
+    successes
+}
+
+// The PROJ temporal gate: When both t_epoch and t_final are given, the shift
+// is only applied to coordinates observed before t_epoch, and only if t_final
+// is after t_epoch. Coordinates without an observation epoch are always shifted
+fn in_temporal_gate(t_obs: f64, t_epoch: f64, t_final: f64) -> bool {
+    if t_epoch.is_nan() || t_final.is_nan() || t_obs.is_nan() {
+        return true;
+    }
+    t_obs < t_epoch && t_final > t_epoch
+}
+
+// ----- C O N S T R U C T O R ------------------------------------------------------
+
+#[rustfmt::skip]
+pub const GAMUT: [OpParameter; 6] = [
+    OpParameter::Flag { key: "inv" },
+    OpParameter::Texts { key: "grids", default: None },
+    OpParameter::Real { key: "multiplier", default: Some(-1.) },
+    OpParameter::Real { key: "t_epoch", default: Some(f64::NAN) },
+    OpParameter::Text { key: "t_final", default: Some("") },
+    OpParameter::Text { key: "interpolation", default: Some("bilinear") },
+];
+
+// The interpolation method, validated by the constructor
+fn interpolation(op: &Op) -> Interpolation {
+    let method = op.params.text("interpolation").unwrap_or_default();
+    method.parse().unwrap_or_default()
+}
+
+// The final epoch, as a decimal year. PROJ's special value "now" is the
+// current epoch, while no value at all is represented by NaN
+fn final_epoch(t_final: &str) -> Result<f64, Error> {
+    match t_final {
+        "" => Ok(f64::NAN),
+        "now" => {
+            let seconds = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|_| Error::General("vgridshift: System time before 1970"))?
+                .as_secs_f64();
+            Ok(1970. + seconds / (365.25 * 86400.))
+        }
+        _ => t_final
+            .parse::<f64>()
+            .map_err(|_| Error::BadParam("t_final".to_string(), t_final.to_string())),
+    }
+}
+
+pub fn new(parameters: &RawParameters, ctx: &dyn Context) -> Result<Op, Error> {
+    let def = &parameters.definition;
+    let mut params = ParsedParameters::new(parameters, &GAMUT)?;
+    params.text("interpolation")?.parse::<Interpolation>()?;
+
+    let t_final = final_epoch(&params.text("t_final")?)?;
+    params.real.insert("t_final", t_final);
+
+    for mut grid_name in params.texts("grids")?.clone() {
+        let optional = grid_name.starts_with('@');
+        if optional {
+            grid_name = grid_name.trim_start_matches('@').to_string();
+        }
+
+        if grid_name == "null" {
+            params.boolean.insert("null_grid");
+            break; // ignore any additional grids after a null grid
+        }
+
+        match ctx.get_grid(&grid_name) {
+            Ok(grid) => {
+                if grid.bands() != 1 {
+                    return Err(Error::Invalid(format!(
+                        "vgridshift: {grid_name} is not a 1 band (geoid) grid"
+                    )));
+                }
+                params.grids.push(grid)
+            }
+            Err(e) => {
+                if !optional {
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    let fwd = InnerOp(fwd);
+    let inv = InnerOp(inv);
+    let descriptor = OpDescriptor::new(def, fwd, Some(inv));
+    let steps = Vec::new();
+    let id = OpHandle::new();
+
+    Ok(Op {
+        descriptor,
+        params,
+        steps,
+        id,
+    })
+}
+
+// ----- T E S T S ------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vgridshift() -> Result<(), Error> {
+        let mut ctx = Plain::default();
+        let cph = Coor4D::geo(55., 12., 100., 0.);
+
+        // The undulation at 55N 12E is 55.12 m in all the test geoids
+        for grid in ["test.geoid", "test.gtx", "test.byn", "test.isg"] {
+            let op = ctx.op(&format!("vgridshift grids={grid}"))?;
+            let mut data = [cph];
+            ctx.apply(op, Fwd, &mut data)?;
+            assert!((data[0][2] - 44.88).abs() < 1e-5);
+            ctx.apply(op, Inv, &mut data)?;
+            assert!((data[0][2] - 100.).abs() < 1e-5);
+        }
+
+        // The multiplier scales the grid values, and determines the sign convention
+        let op = ctx.op("vgridshift grids=test.geoid multiplier=0.5")?;
+        let mut data = [cph];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][2] - 127.56).abs() < 1e-5);
+        ctx.apply(op, Inv, &mut data)?;
+        assert!((data[0][2] - 100.).abs() < 1e-5);
+
+        // Horizontal shift grids are not geoids
+        assert!(ctx.op("vgridshift grids=test.datum").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn outside_of_grid() -> Result<(), Error> {
+        let mut ctx = Plain::default();
+        let ldn = Coor4D::geo(51.505, -0.09, 100., 0.);
+
+        let op = ctx.op("vgridshift grids=test.geoid")?;
+        let mut data = [ldn];
+        assert_eq!(ctx.apply(op, Fwd, &mut data)?, 0);
+        assert!(data[0][2].is_nan());
+
+        let op = ctx.op("vgridshift grids=test.geoid,@null")?;
+        let mut data = [ldn];
+        assert_eq!(ctx.apply(op, Fwd, &mut data)?, 1);
+        assert_eq!(data[0][2], 100.);
+        Ok(())
+    }
+
+    #[test]
+    fn temporal_gate() -> Result<(), Error> {
+        let mut ctx = Plain::default();
+        let op = ctx.op("vgridshift grids=test.geoid t_epoch=2010 t_final=2020")?;
+
+        // Observed before t_epoch: Shifted
+        let mut data = [Coor4D::geo(55., 12., 100., 2005.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][2] - 44.88).abs() < 1e-5);
+
+        // Observed after t_epoch: Unchanged
+        let mut data = [Coor4D::geo(55., 12., 100., 2015.)];
+        assert_eq!(ctx.apply(op, Fwd, &mut data)?, 1);
+        assert_eq!(data[0][2], 100.);
+
+        // t_final before t_epoch: Unchanged
+        let op = ctx.op("vgridshift grids=test.geoid t_epoch=2010 t_final=2000")?;
+        let mut data = [Coor4D::geo(55., 12., 100., 2005.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert_eq!(data[0][2], 100.);
+
+        // "now" is certainly after 2010
+        let op = ctx.op("vgridshift grids=test.geoid t_epoch=2010 t_final=now")?;
+        let mut data = [Coor4D::geo(55., 12., 100., 2005.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][2] - 44.88).abs() < 1e-5);
+
+        assert!(ctx.op("vgridshift grids=test.geoid t_final=then").is_err());
+        Ok(())
+    }
+}
@@ -36,7 +36,19 @@ pub mod authoring {
 pub mod ctx {
     pub use crate::context::minimal::Minimal;
     #[cfg(feature = "with_plain")]
+    pub use crate::context::plain::GridCacheStats;
+    #[cfg(feature = "with_plain")]
     pub use crate::context::plain::Plain;
+    #[cfg(feature = "with_plain")]
+    pub use crate::context::plain::DATA_PATH_VARIABLE;
+    #[cfg(feature = "with_plain")]
+    pub use crate::context::plain::DEFAULT_GRID_CACHE_SIZE;
+    #[cfg(feature = "with_plain")]
+    pub use crate::context::plain::DEFAULT_GRID_SIZE_THRESHOLD;
+    #[cfg(feature = "proj_db")]
+    pub use crate::context::proj_db::ProjDb;
+    #[cfg(feature = "with_plain")]
+    pub use crate::context::shared::Shared;
     pub use crate::context::Context;
     pub use crate::op::OpHandle;
     pub use crate::Direction;
@@ -68,6 +80,7 @@ pub mod coord {
     pub use crate::coordinate::tuple::CoordinateTuple;
     pub use crate::coordinate::AngularUnits;
     pub use crate::coordinate::CoordinateMetadata;
+    pub use crate::coordinate::Crs;
     pub use crate::math::angular;
 }
 
@@ -84,10 +97,16 @@ mod ops {
 
 /// Elements for handling grids
 mod grd {
+    pub use crate::grid::file::FileGrid;
+    pub use crate::grid::geotiff::GeoTiffGrid;
     pub use crate::grid::grids_at;
+    pub use crate::grid::grids_at_with;
+    pub use crate::grid::grids_at_with_accuracy;
     pub use crate::grid::ntv2::Ntv2Grid;
     pub use crate::grid::BaseGrid;
     pub use crate::grid::Grid;
+    pub use crate::grid::GridInfo;
+    pub use crate::grid::Interpolation;
 }
 
 /// Elements for parsing both Geodesy and PROJ syntax
@@ -147,6 +166,10 @@ pub enum Error {
     #[error("UTF8 error")]
     Utf8Error(#[from] std::str::Utf8Error),
 
+    #[cfg(feature = "proj_db")]
+    #[error("database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
     #[error("Unknown")]
     Unknown,
 }
@@ -71,11 +71,12 @@ pub fn qs(sinphi: f64, e: f64) -> f64 {
         return 2.0 * sinphi;
     }
 
+    // ln((1 - con) / (1 + con)) = -2 atanh(con), but the latter does not
+    // lose precision when the argument of the logarithm is close to 1
     let con = e * sinphi;
     let div1 = 1.0 - con * con;
-    let div2 = 1.0 + con;
 
-    one_es * (sinphi / div1 - (0.5 / e) * ((1. - con) / div2).ln())
+    one_es * (sinphi / div1 + con.atanh() / e)
 }
 
 /// Ancillary function for computing the inverse isometric latitude. Follows
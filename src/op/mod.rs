@@ -18,6 +18,18 @@ impl OpHandle {
     pub fn new() -> Self {
         OpHandle(uuid::Uuid::new_v4())
     }
+
+    // A random handle, carrying the storage slot number `slot`, for contexts
+    // looking up operators by slot, rather than by handle
+    pub(crate) fn with_slot(slot: usize) -> Self {
+        let (random, _) = uuid::Uuid::new_v4().as_u64_pair();
+        OpHandle(uuid::Uuid::from_u64_pair(random, slot as u64))
+    }
+
+    // The slot number carried by a handle made by `with_slot`
+    pub(crate) fn slot(&self) -> usize {
+        self.0.as_u64_pair().1 as usize
+    }
 }
 impl Default for OpHandle {
     fn default() -> Self {
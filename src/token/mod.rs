@@ -212,6 +212,13 @@ where
 /// have the scaling defined as `k` instead of `k_0`.
 /// - *parse_proj* will replace `k` with `k_0` whenever it is encountered.
 ///
+/// ## Geoid grids via `geoidgrids` parameter
+/// - PROJ supports the legacy `geoidgrids` parameter for indicating that a
+///   definition expects orthometric heights.
+/// - *parse_proj* will remove the `geoidgrids` parameter and insert a
+///   `vgridshift` step using the same grids ahead of the step itself (or after
+///   it, for inverted steps).
+///
 pub fn parse_proj(definition: &str) -> Result<String, Error> {
     // If it doesn't look like a PROJ string, we return it unchanged
     if definition.contains('|') | !definition.contains("proj") {
@@ -320,6 +327,13 @@ pub fn parse_proj(definition: &str) -> Result<String, Error> {
 
         tidy_proj(&mut elements)?;
 
+        // PROJ's legacy `geoidgrids` parameter indicates orthometric heights,
+        // which we handle by an explicit `vgridshift` step before the step itself
+        let geoidgrids = elements
+            .iter()
+            .position(|x| x.starts_with("geoidgrids="))
+            .map(|i| elements.remove(i)[11..].to_string());
+
         // Skip empty steps, insert pipeline globals, handle step and pipeline
         // inversions, and handle directional omissions (omit_fwd, omit_inv)
         let mut geodesy_step = elements.join(" ").trim().to_string();
@@ -345,10 +359,19 @@ pub fn parse_proj(definition: &str) -> Result<String, Error> {
             }
 
             geodesy_step = elements.join(" ").trim().to_string();
+            let mut group = vec![geodesy_step];
+            if let Some(grids) = &geoidgrids {
+                if step_is_inverted != pipeline_is_inverted {
+                    group.push(format!("vgridshift inv grids={grids}"));
+                } else {
+                    group.insert(0, format!("vgridshift grids={grids}"));
+                }
+            }
+
             if pipeline_is_inverted {
-                geodesy_steps.insert(0, geodesy_step);
+                geodesy_steps.splice(0..0, group);
             } else {
-                geodesy_steps.push(geodesy_step);
+                geodesy_steps.extend(group);
             }
         }
     }
@@ -585,4 +608,31 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn geoidgrids() -> Result<(), Error> {
+        // The geoid is applied on the geographical side of the step
+        assert_eq!(
+            parse_proj("+proj=utm +zone=32 +geoidgrids=egm96_15.gtx")?,
+            "vgridshift grids=egm96_15.gtx | utm zone=32"
+        );
+        assert_eq!(
+            parse_proj("+proj=pipeline +step +inv +proj=utm +zone=32 +geoidgrids=a.gtx +step +proj=utm +zone=33")?,
+            "utm inv zone=32 | vgridshift inv grids=a.gtx | utm zone=33"
+        );
+
+        // ...also when the entire pipeline is inverted
+        assert_eq!(
+            parse_proj("+proj=pipeline +inv +step +proj=utm +zone=32 +geoidgrids=a.gtx +step +proj=utm +zone=33")?,
+            "utm inv zone=33 | utm inv zone=32 | vgridshift inv grids=a.gtx"
+        );
+
+        // And the result is instantiable
+        let mut ctx = Plain::default();
+        let op = ctx.op(&parse_proj("+proj=latlong +geoidgrids=test.gtx")?)?;
+        let mut data = [Coor4D::geo(55., 12., 100., 0.)];
+        ctx.apply(op, Fwd, &mut data)?;
+        assert!((data[0][2] - 44.88).abs() < 1e-5);
+        Ok(())
+    }
 }